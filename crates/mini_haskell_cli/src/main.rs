@@ -1,17 +1,308 @@
-use clap::Parser;
+mod session;
+mod source;
+
+use crate::session::Session;
+use crate::source::Source;
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use miette::Error;
+use mini_haskell_diagnostic::lint::{self, Level, Lint, LintLevels, UnknownLint};
+use mini_haskell_diagnostic::span::{Encoding, LineIndex};
+use mini_haskell_diagnostic::{codes, fix};
+use mini_haskell_interpreter::interpreter::{Interpreter, RECURSION_LIMIT, STACK_PER_LEVEL};
+use mini_haskell_lexer::lexer::{TokenTy, Tokenizer};
+use mini_haskell_parser::ast::Program;
+use mini_haskell_parser::error::ParseError;
+use mini_haskell_parser::fmt::AstView;
+use mini_haskell_typeck::checker::TypeChecker;
+use mini_haskell_typeck::lint::Linter;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about = "Mini Haskell Compiler", long_about = None)]
+#[command(name = "mhc", author, version, about = "Mini Haskell Compiler", long_about = None)]
 struct Args {
-    /// AST Dump
-    #[arg(long, action = clap::ArgAction::SetTrue)]
-    ast: bool,
+    #[command(flatten)]
+    global: Global,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Options shared by every subcommand
+#[derive(clap::Args, Debug)]
+pub struct Global {
+    /// Whether to color diagnostics
+    #[arg(long, value_enum, global = true, default_value_t = Color::Auto)]
+    pub color: Color,
+
+    /// How to print the result of a stage
+    #[arg(long, value_enum, global = true, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// How to print diagnostics
+    #[arg(long, value_enum, global = true, default_value_t = ErrorFormat::Human)]
+    pub error_format: ErrorFormat,
+
+    /// Library rendering human readable diagnostics
+    #[arg(long, value_enum, global = true, default_value_t = Renderer::Miette)]
+    pub renderer: Renderer,
+
+    /// Do not report a lint, given by name or code
+    #[arg(short = 'A', long = "allow", value_name = "LINT", value_parser = lint, global = true)]
+    pub allow: Vec<&'static Lint>,
+
+    /// Report a lint as a warning, overriding --allow
+    #[arg(short = 'W', long = "warn", value_name = "LINT", value_parser = lint, global = true)]
+    pub warn: Vec<&'static Lint>,
+
+    /// Report a lint as an error, overriding --allow and --warn
+    #[arg(short = 'D', long = "deny", value_name = "LINT", value_parser = lint, global = true)]
+    pub deny: Vec<&'static Lint>,
+
+    /// Report every warning as an error
+    #[arg(long, global = true)]
+    pub deny_warnings: bool,
+}
+
+impl Global {
+    pub fn levels(&self) -> LintLevels {
+        let mut levels = LintLevels::new();
+        for (lints, level) in [
+            (&self.allow, Level::Allow),
+            (&self.warn, Level::Warn),
+            (&self.deny, Level::Deny),
+        ] {
+            for lint in lints {
+                levels.set(lint.code, level).unwrap();
+            }
+        }
+        levels.deny_warnings(self.deny_warnings);
+        levels
+    }
+}
+
+fn lint(name: &str) -> Result<&'static Lint, UnknownLint> {
+    lint::find(name).ok_or_else(|| UnknownLint(name.to_string()))
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable output
+    Text,
+    /// Rust debug representation of the internal data structures
+    Debug,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Renderer {
+    Miette,
+    Ariadne,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Rendered diagnostics with source snippets
+    Human,
+    /// One JSON object per diagnostic and line
+    Json,
+    /// A SARIF 2.1.0 log
+    Sarif,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the tokens of a program
+    Lex(Input),
+    /// Parse a program and print its AST
+    Parse(Input),
+    /// Parse and type check a program
+    Check(Input),
+    /// Type check and evaluate a program, printing every top-level variable
+    Run(RunArgs),
+    /// Print a program in canonical layout
+    Fmt(Input),
+    /// Apply the suggested fixes of lexing and syntax errors, rewriting the file in place
+    Fix(Input),
+    /// Print the detailed explanation of a diagnostic code, like `MH0001`
+    Explain { code: String },
+}
+
+#[derive(clap::Args, Debug)]
+struct Input {
+    /// Source file, `-` reads from stdin
+    path: PathBuf,
 }
 
-fn main() {
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Maximum depth of nested expressions, the body of a called function
+    /// nesting inside the call
+    #[arg(
+        long,
+        default_value_t = RECURSION_LIMIT,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..=MAX_RECURSION_LIMIT)
+    )]
+    recursion_limit: usize,
+}
+
+/// Largest `--recursion-limit`, its stack of 4 GiB can still be reserved
+const MAX_RECURSION_LIMIT: u64 = 1 << 20;
+
+/// Number of times `mhc fix` reparses the fixed source looking for more fixes
+const MAX_FIX_PASSES: usize = 8;
+
+fn main() -> ExitCode {
     let args = Args::parse();
+    let mut session = Session::new(&args.global);
+    let result = execute(&mut session, &args.command);
+    // diagnostics are printed even if a stage failed
+    let code = session.finish();
+    result.err().unwrap_or(code)
+}
+
+fn execute(session: &mut Session, command: &Command) -> Result<(), ExitCode> {
+    match command {
+        Command::Lex(input) => lex(session, &Source::read(&input.path)?),
+        Command::Parse(input) => parse(session, &Source::read(&input.path)?),
+        Command::Check(input) => check(session, &Source::read(&input.path)?).map(|_| ()),
+        Command::Run(args) => run(
+            session,
+            &Source::read(&args.input.path)?,
+            args.recursion_limit,
+        ),
+        Command::Fmt(input) => fmt(session, &Source::read(&input.path)?),
+        Command::Fix(input) => fix(session, &mut Source::read(&input.path)?),
+        Command::Explain { code } => explain(code),
+    }
+}
+
+fn lex(session: &mut Session, source: &Source) -> Result<(), ExitCode> {
+    let index = LineIndex::new(&source.text);
+    let mut errors = Vec::new();
+    for token in Tokenizer::new(&source.text) {
+        match token {
+            Ok(token) => match session.global.format {
+                Format::Text => {
+                    let (start, end) = index.span(Encoding::Utf32, token.span).unwrap();
+                    match token.ty {
+                        // the text of these tokens is not part of their type
+                        TokenTy::NatLit | TokenTy::Comment => {
+                            let text = &source.text[token.span.start..token.span.end];
+                            println!("{start}-{end} {:?}({text:?})", token.ty)
+                        }
+                        ty => println!("{start}-{end} {ty:?}"),
+                    }
+                }
+                Format::Debug => println!("{token:#?}"),
+            },
+            Err(err) => errors.push(err),
+        }
+    }
+    session.report(source, errors)
+}
+
+fn parse_program(session: &mut Session, source: &Source) -> Result<Program, ExitCode> {
+    let (program, errors) = mini_haskell_parser::parser::Parser::parse(&source.text);
+    session.report(source, errors)?;
+    Ok(program)
+}
+
+fn parse(session: &mut Session, source: &Source) -> Result<(), ExitCode> {
+    let program = parse_program(session, source)?;
+    match session.global.format {
+        Format::Text => println!("{}", program.ast()),
+        Format::Debug => println!("{program:#?}"),
+    }
+    Ok(())
+}
+
+fn check(session: &mut Session, source: &Source) -> Result<Program, ExitCode> {
+    let program = parse_program(session, source)?;
+    let errors = TypeChecker::check(&program).into_iter().map(Error::from);
+    let warnings = Linter::lint(&program).into_iter().map(Error::from);
+    session.report(source, errors.chain(warnings))?;
+    Ok(program)
+}
+
+fn run(session: &mut Session, source: &Source, recursion_limit: usize) -> Result<(), ExitCode> {
+    let program = check(session, source)?;
+    // the interpreter recurses natively, give it enough stack for the limit
+    let result = thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(recursion_limit * STACK_PER_LEVEL)
+            .spawn_scoped(scope, || {
+                Interpreter::new()
+                    .with_recursion_limit(recursion_limit)
+                    .program(&program)
+            })
+            .map(|interpreter| interpreter.join().unwrap())
+    });
+    let values = match result {
+        Ok(Ok(values)) => values,
+        Ok(Err(err)) => return session.report(source, [err]),
+        Err(err) => {
+            eprintln!("error: can not start the interpreter: {err}");
+            return Err(ExitCode::FAILURE);
+        }
+    };
+    for (name, value) in values {
+        match session.global.format {
+            Format::Text => println!("{name} = {value}"),
+            Format::Debug => println!("{name} = {value:#?}"),
+        }
+    }
+    Ok(())
+}
+
+fn fmt(session: &mut Session, source: &Source) -> Result<(), ExitCode> {
+    let program = parse_program(session, source)?;
+    print!("{program}");
+    Ok(())
+}
+
+fn fix(session: &mut Session, source: &mut Source) -> Result<(), ExitCode> {
+    let mut text = source.text.clone();
+    let mut applied = 0;
+    // a fix may let the parser get further and find more errors
+    for _ in 0..MAX_FIX_PASSES {
+        let (_, errors) = mini_haskell_parser::parser::Parser::parse(&text);
+        let suggestions = errors.iter().filter_map(ParseError::suggestion);
+        let (fixed, count) = fix::apply(&text, suggestions);
+        if count == 0 {
+            break;
+        }
+        text = fixed;
+        applied += count;
+    }
+    source.write(text)?;
+    if applied > 0 {
+        eprintln!("applied {applied} fix(es) to {}", source.path.display());
+    }
+    // whatever could not be fixed automatically
+    parse_program(session, source).map(|_| ())
+}
 
-    if args.ast {
-        println!("TODO: AST dump");
+fn explain(code: &str) -> Result<(), ExitCode> {
+    match codes::explain(code) {
+        Some(explanation) => {
+            println!("# {}\n\n{explanation}", code.to_ascii_uppercase());
+            Ok(())
+        }
+        None => {
+            eprintln!("error: `{code}` is not a mini-haskell diagnostic code");
+            Err(ExitCode::FAILURE)
+        }
     }
 }
@@ -0,0 +1,89 @@
+use crate::source::Source;
+use crate::{Color, ErrorFormat, Global, Renderer};
+use miette::{Error, GraphicalTheme};
+use mini_haskell_diagnostic::reporter::Reporter;
+use mini_haskell_diagnostic::source::FileId;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+
+/// State of one invocation of `mhc`, collecting the diagnostics of every stage
+/// so that they are printed once, as a single log, by [`Session::finish`]
+pub struct Session<'a> {
+    pub global: &'a Global,
+    reporter: Reporter,
+    /// The source diagnostics were last reported for
+    file: Option<FileId>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(global: &'a Global) -> Self {
+        let reporter = match global.renderer {
+            Renderer::Miette => Reporter::themed(theme(global.color)),
+            Renderer::Ariadne => {
+                Reporter::ariadne(ariadne::Config::default().with_color(colored(global.color)))
+            }
+        };
+        Session {
+            global,
+            reporter: reporter.with_levels(global.levels()),
+            file: None,
+        }
+    }
+
+    /// Record `diagnostics` of `source`, failing if any error has been
+    /// reported so far, which stops the invocation before its next stage
+    pub fn report<E: Into<Error>>(
+        &mut self,
+        source: &Source,
+        diagnostics: impl IntoIterator<Item = E>,
+    ) -> Result<(), ExitCode> {
+        let file = match self.file {
+            // `mhc fix` reports again after rewriting the source
+            Some(file) if self.reporter.sources().get(file).text() == source.text => file,
+            _ => self
+                .reporter
+                .sources_mut()
+                .add(source.path.to_string_lossy(), source.text.as_str()),
+        };
+        self.file = Some(file);
+        self.reporter.report((file, diagnostics).into());
+        match self.reporter.has_errors() {
+            true => Err(ExitCode::FAILURE),
+            false => Ok(()),
+        }
+    }
+
+    /// Print every reported diagnostic to stderr, failing if any of them is an
+    /// error
+    pub fn finish(self) -> ExitCode {
+        let reporter = self.reporter;
+        match self.global.error_format {
+            ErrorFormat::Human => {
+                if let Some(summary) = reporter.summary() {
+                    eprintln!("{}{summary}", reporter.string());
+                }
+            }
+            ErrorFormat::Json => eprint!("{}", reporter.json()),
+            ErrorFormat::Sarif => eprintln!("{}", reporter.sarif("mhc", env!("CARGO_PKG_VERSION"))),
+        }
+        match reporter.has_errors() {
+            true => ExitCode::FAILURE,
+            false => ExitCode::SUCCESS,
+        }
+    }
+}
+
+fn colored(color: Color) -> bool {
+    match color {
+        Color::Always => true,
+        Color::Never => false,
+        Color::Auto => io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+fn theme(color: Color) -> GraphicalTheme {
+    match colored(color) {
+        true => GraphicalTheme::unicode(),
+        false => GraphicalTheme::unicode_nocolor(),
+    }
+}
@@ -0,0 +1,56 @@
+use mini_haskell_diagnostic::source::STDIN;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// A source file read from disk or stdin
+pub struct Source {
+    pub path: PathBuf,
+    pub text: String,
+    stdin: bool,
+}
+
+impl Source {
+    pub fn read(path: &Path) -> Result<Self, ExitCode> {
+        let stdin = path == Path::new("-");
+        let text = if stdin {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        } else {
+            fs::read_to_string(path)
+        };
+        match text {
+            Ok(text) => Ok(Source {
+                path: match stdin {
+                    true => PathBuf::from(STDIN),
+                    false => path.to_path_buf(),
+                },
+                text,
+                stdin,
+            }),
+            Err(err) => {
+                eprintln!("error: can not read {}: {err}", path.display());
+                Err(ExitCode::FAILURE)
+            }
+        }
+    }
+
+    /// Replace the text of the source on disk, leaving the file untouched if
+    /// it is unchanged. A source read from stdin is always printed to stdout.
+    pub fn write(&mut self, text: String) -> Result<(), ExitCode> {
+        let changed = text != self.text;
+        self.text = text;
+        if self.stdin {
+            print!("{}", self.text);
+            return Ok(());
+        }
+        if !changed {
+            return Ok(());
+        }
+        fs::write(&self.path, &self.text).map_err(|err| {
+            eprintln!("error: can not write {}: {err}", self.path.display());
+            ExitCode::FAILURE
+        })
+    }
+}
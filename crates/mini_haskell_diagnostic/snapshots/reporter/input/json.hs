@@ -0,0 +1,2 @@
+nat é = 1;
+Hello World!
\ No newline at end of file
@@ -0,0 +1 @@
+bool y = T;
@@ -0,0 +1,4 @@
+nat é = 1;
+[ 𝔸 ] T
+
+x
\ No newline at end of file
@@ -0,0 +1,70 @@
+//! Registry of the stable diagnostic codes and their long-form explanations.
+//!
+//! Codes are never reused: once a diagnostic is removed, its code is retired
+//! and stays in the registry.
+
+/// Every known code along with its explanation, sorted by code
+pub const CODES: &[(&str, &str)] = &[
+    ("MH0001", include_str!("codes/MH0001.md")),
+    ("MH0002", include_str!("codes/MH0002.md")),
+    ("MH0003", include_str!("codes/MH0003.md")),
+    ("MH0004", include_str!("codes/MH0004.md")),
+    ("MH0005", include_str!("codes/MH0005.md")),
+    ("MH0006", include_str!("codes/MH0006.md")),
+    ("MH0007", include_str!("codes/MH0007.md")),
+    ("MH0008", include_str!("codes/MH0008.md")),
+    ("MH0009", include_str!("codes/MH0009.md")),
+    ("MH0010", include_str!("codes/MH0010.md")),
+    ("MH0011", include_str!("codes/MH0011.md")),
+    ("MH0012", include_str!("codes/MH0012.md")),
+    ("MH0013", include_str!("codes/MH0013.md")),
+    ("MH0014", include_str!("codes/MH0014.md")),
+    ("MH0015", include_str!("codes/MH0015.md")),
+    ("MH0016", include_str!("codes/MH0016.md")),
+    ("MH0017", include_str!("codes/MH0017.md")),
+    ("MH0018", include_str!("codes/MH0018.md")),
+    ("MH0019", include_str!("codes/MH0019.md")),
+    ("MH0020", include_str!("codes/MH0020.md")),
+    ("MH0021", include_str!("codes/MH0021.md")),
+    ("MH0022", include_str!("codes/MH0022.md")),
+    ("MH0023", include_str!("codes/MH0023.md")),
+];
+
+/// The explanation of `code`, which is matched case-insensitively
+pub fn explain(code: &str) -> Option<&'static str> {
+    CODES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// The first paragraph of the explanation of `code`, on a single line
+pub fn summary(code: &str) -> Option<String> {
+    let explanation = explain(code)?;
+    let paragraph = explanation.split("\n\n").next().unwrap_or(explanation);
+    Some(paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codes::{explain, summary, CODES};
+
+    #[test]
+    fn sorted() {
+        assert!(CODES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(explain("MH0001"), Some(CODES[0].1));
+        assert_eq!(explain("mh0002"), Some(CODES[1].1));
+        assert_eq!(explain("MH9999"), None);
+        assert_eq!(
+            summary("MH0005").as_deref(),
+            Some(
+                "The parser expected a particular construct, such as an expression, \
+                 a type or a declaration, but found something else."
+            )
+        );
+    }
+}
@@ -1,19 +1,20 @@
 use crate::reporter::{DiagnosticTuple, Reporter};
 use miette::Error;
-use std::fs;
 use std::path::Path;
 
-pub fn diagnostic<P, T, I, F>(path: P, f: F) -> String
+/// Render the diagnostics `f` produces for `src`, labelled with `path`
+pub fn diagnostic<P, T, I, F>(path: P, src: &str, f: F) -> String
 where
     P: AsRef<Path>,
     T: Into<Error>,
     I: IntoIterator<Item = T>,
     F: Fn(&str) -> I,
 {
-    let src = fs::read_to_string(path.as_ref()).unwrap();
-    let diagnostic: DiagnosticTuple = (path, f(&src)).into();
-
     let mut reporter = Reporter::new();
+    let file = reporter
+        .sources_mut()
+        .add(path.as_ref().to_string_lossy(), src);
+    let diagnostic: DiagnosticTuple = (file, f(src)).into();
     reporter.report(diagnostic);
 
     reporter.string()
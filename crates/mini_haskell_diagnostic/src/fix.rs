@@ -0,0 +1,65 @@
+//! Machine-applicable suggestions and their application to a source text.
+
+use crate::span::Span;
+use std::fmt::{Display, Formatter};
+
+/// An edit replacing the text at `span` with `replacement`. An empty `span`
+/// inserts `replacement` and an empty `replacement` removes the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: &'static str,
+}
+
+impl Suggestion {
+    pub fn insert(offset: usize, text: &'static str) -> Self {
+        Suggestion {
+            span: Span {
+                start: offset,
+                end: offset,
+            },
+            replacement: text,
+        }
+    }
+
+    pub fn remove(span: Span) -> Self {
+        Suggestion {
+            span,
+            replacement: "",
+        }
+    }
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let replacement = self.replacement.trim();
+        match (self.span.start == self.span.end, replacement.is_empty()) {
+            (true, _) => write!(f, "Insert `{replacement}`"),
+            (false, true) => write!(f, "Remove this token"),
+            (false, false) => write!(f, "Replace this token with `{replacement}`"),
+        }
+    }
+}
+
+/// Apply `suggestions` to `src`. Suggestions overlapping an earlier one are
+/// skipped, returning the new text along with the number of applied edits.
+pub fn apply(src: &str, suggestions: impl IntoIterator<Item = Suggestion>) -> (String, usize) {
+    let mut suggestions = suggestions.into_iter().collect::<Vec<_>>();
+    suggestions.sort_by_key(|suggestion| (suggestion.span.start, suggestion.span.end));
+
+    let mut fixed = String::with_capacity(src.len());
+    let mut applied = 0;
+    // end of the text copied or replaced so far
+    let mut copied = 0;
+    for suggestion in suggestions {
+        if suggestion.span.start < copied || suggestion.span.end > src.len() {
+            continue;
+        }
+        fixed.push_str(&src[copied..suggestion.span.start]);
+        fixed.push_str(suggestion.replacement);
+        copied = suggestion.span.end;
+        applied += 1;
+    }
+    fixed.push_str(&src[copied..]);
+    (fixed, applied)
+}
@@ -0,0 +1,84 @@
+//! Machine-readable rendering of diagnostics, one JSON object per diagnostic.
+
+use crate::source::SourceFile;
+use crate::span::{Encoding, LineCol};
+use miette::{Diagnostic, Severity};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct JsonDiagnostic<'src> {
+    pub file: &'src str,
+    pub code: Option<String>,
+    pub severity: &'static str,
+    pub message: String,
+    pub help: Option<String>,
+    /// The first label is the primary one, the others are secondary
+    pub labels: Vec<JsonLabel>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonLabel {
+    pub primary: bool,
+    pub message: Option<String>,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub start: JsonPosition,
+    pub end: JsonPosition,
+}
+
+/// One-based line and column, with the column counted in UTF-16 code units
+/// like most editors do
+#[derive(Debug, Serialize)]
+pub struct JsonPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<LineCol> for JsonPosition {
+    fn from(value: LineCol) -> Self {
+        JsonPosition {
+            line: value.line + 1,
+            column: value.col + 1,
+        }
+    }
+}
+
+impl<'src> JsonDiagnostic<'src> {
+    pub fn new(file: &'src SourceFile, diagnostic: &dyn Diagnostic) -> Self {
+        let index = file.line_index();
+        let labels = diagnostic
+            .labels()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, label)| {
+                let (byte_start, byte_end) = (label.offset(), label.offset() + label.len());
+                let position = |offset| {
+                    let line_col = index.line_col(offset).unwrap_or_default();
+                    JsonPosition::from(index.to_wide(Encoding::Utf16, line_col))
+                };
+                JsonLabel {
+                    primary: i == 0,
+                    message: label.label().map(str::to_owned),
+                    byte_start,
+                    byte_end,
+                    start: position(byte_start),
+                    end: position(byte_end),
+                }
+            })
+            .collect();
+
+        JsonDiagnostic {
+            file: file.name(),
+            code: diagnostic.code().map(|code| code.to_string()),
+            severity: match diagnostic.severity().unwrap_or(Severity::Error) {
+                Severity::Advice => "advice",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            },
+            message: diagnostic.to_string(),
+            help: diagnostic.help().map(|help| help.to_string()),
+            labels,
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod codes;
 pub mod diagnostic;
+pub mod fix;
+pub mod json;
+pub mod lint;
 pub mod reporter;
+pub mod sarif;
+pub mod source;
 pub mod span;
@@ -0,0 +1,178 @@
+//! Lints are categories of warnings that can be allowed, kept as warnings or
+//! turned into errors.
+
+use miette::{Diagnostic, Error, LabeledSpan, Severity, SourceCode};
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Do not report the lint at all
+    Allow,
+    /// Report the lint as a warning
+    Warn,
+    /// Report the lint as an error
+    Deny,
+}
+
+#[derive(Debug)]
+pub struct Lint {
+    pub name: &'static str,
+    /// The diagnostic code of the warnings reported by this lint
+    pub code: &'static str,
+    pub default: Level,
+}
+
+/// Every known lint
+pub const LINTS: &[Lint] = &[
+    Lint {
+        name: "unused_parameters",
+        code: "MH0017",
+        default: Level::Warn,
+    },
+    Lint {
+        name: "constant_condition",
+        code: "MH0018",
+        default: Level::Warn,
+    },
+];
+
+/// Find a lint by its name or code. Dashes in names are accepted in place of
+/// underscores, and codes are matched case-insensitively.
+pub fn find(lint: &str) -> Option<&'static Lint> {
+    let name = lint.replace('-', "_");
+    LINTS
+        .iter()
+        .find(|known| known.name == name || known.code.eq_ignore_ascii_case(lint))
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("unknown lint `{0}`")]
+pub struct UnknownLint(pub String);
+
+/// The level of every lint, as configured by the user
+#[derive(Debug, Clone, Default)]
+pub struct LintLevels {
+    levels: HashMap<&'static str, Level>,
+    deny_warnings: bool,
+}
+
+impl LintLevels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level of `lint`, given by name or code
+    pub fn set(&mut self, lint: &str, level: Level) -> Result<&mut Self, UnknownLint> {
+        let lint = find(lint).ok_or_else(|| UnknownLint(lint.to_string()))?;
+        self.levels.insert(lint.code, level);
+        Ok(self)
+    }
+
+    /// Report every warning as an error
+    pub fn deny_warnings(&mut self, deny: bool) -> &mut Self {
+        self.deny_warnings = deny;
+        self
+    }
+
+    /// The level of the lint with diagnostic `code`, diagnostics which are not
+    /// lints are always reported
+    pub fn level(&self, code: &str) -> Level {
+        let level = match LINTS.iter().find(|lint| lint.code == code) {
+            Some(lint) => *self.levels.get(lint.code).unwrap_or(&lint.default),
+            None => Level::Warn,
+        };
+        match (level, self.deny_warnings) {
+            (Level::Warn, true) => Level::Deny,
+            (level, _) => level,
+        }
+    }
+
+    /// Apply the configured level to `diagnostic`, returning `None` if it is
+    /// allowed. Only warnings are affected.
+    pub fn apply(&self, diagnostic: Error) -> Option<Error> {
+        if diagnostic.severity() != Some(Severity::Warning) {
+            return Some(diagnostic);
+        }
+        let code = diagnostic.code().map(|code| code.to_string());
+        match self.level(code.as_deref().unwrap_or_default()) {
+            Level::Allow => None,
+            Level::Warn => Some(diagnostic),
+            Level::Deny => Some(Denied(diagnostic).into()),
+        }
+    }
+}
+
+/// A warning reported as an error
+struct Denied(Error);
+
+impl fmt::Debug for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for Denied {}
+
+impl Diagnostic for Denied {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.0.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(Severity::Error)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.0.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.0.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.0.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.0.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.0.related()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lint::{find, Level, LintLevels, UnknownLint};
+
+    #[test]
+    fn levels() {
+        let mut levels = LintLevels::new();
+        assert_eq!(levels.level("MH0017"), Level::Warn);
+        assert_eq!(levels.level("MH0001"), Level::Warn);
+
+        levels.set("unused-parameters", Level::Allow).unwrap();
+        levels.set("mh0018", Level::Deny).unwrap();
+        assert_eq!(levels.level("MH0017"), Level::Allow);
+        assert_eq!(levels.level("MH0018"), Level::Deny);
+        assert_eq!(
+            levels.set("unused", Level::Deny).unwrap_err(),
+            UnknownLint("unused".to_string())
+        );
+
+        levels.deny_warnings(true);
+        levels.set("unused_parameters", Level::Warn).unwrap();
+        assert_eq!(levels.level("MH0017"), Level::Deny);
+        assert_eq!(find("MH0017").unwrap().name, "unused_parameters");
+    }
+}
@@ -1,16 +1,52 @@
-use miette::{Error, GraphicalReportHandler, GraphicalTheme, NamedSource};
-use std::fmt::Write;
-use std::fs;
-use std::path::{Path, PathBuf};
+use crate::json::JsonDiagnostic;
+use crate::lint::LintLevels;
+use crate::sarif::SarifLog;
+use crate::source::{FileId, SourceDb};
+use ariadne::{Cache, ColorGenerator, IndexType, Label, Report, ReportKind, Source};
+use miette::{Error, GraphicalReportHandler, GraphicalTheme, Severity};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Write};
+use std::ops::Range;
 use std::sync::Arc;
 
+/// The sources of a [`SourceDb`] for ariadne, looked up by [`FileId`] so
+/// that files of the same name stay apart, the name is only displayed
+struct AriadneSources<'a> {
+    db: &'a SourceDb,
+    sources: HashMap<FileId, Source<&'a str>>,
+}
+
+impl<'a> AriadneSources<'a> {
+    fn new(db: &'a SourceDb) -> Self {
+        let sources = db
+            .files()
+            .map(|(id, file)| (id, Source::from(file.text())))
+            .collect();
+        Self { db, sources }
+    }
+}
+
+impl<'a> Cache<FileId> for AriadneSources<'a> {
+    type Storage = &'a str;
+
+    fn fetch(&mut self, id: &FileId) -> Result<&Source<&'a str>, Box<dyn Debug + '_>> {
+        self.sources
+            .get(id)
+            .ok_or_else(|| Box::new(format!("unknown file {id:?}")) as _)
+    }
+
+    fn display<'b>(&self, id: &'b FileId) -> Option<Box<dyn Display + 'b>> {
+        Some(Box::new(self.db.get(*id).name().to_string()))
+    }
+}
+
 pub struct DiagnosticTuple {
-    path: PathBuf,
+    file: FileId,
     errors: Vec<Error>,
 }
 
-impl<E: Into<Error>, T: IntoIterator<Item = E>, P: AsRef<Path>> From<(P, T)> for DiagnosticTuple {
-    fn from(val: (P, T)) -> Self {
+impl<E: Into<Error>, T: IntoIterator<Item = E>> From<(FileId, T)> for DiagnosticTuple {
+    fn from(val: (FileId, T)) -> Self {
         let mut builder = DiagnosticTupleBuilder::new(val.0);
         builder.diagnoses(val.1);
         builder.build()
@@ -18,14 +54,14 @@ impl<E: Into<Error>, T: IntoIterator<Item = E>, P: AsRef<Path>> From<(P, T)> for
 }
 
 pub struct DiagnosticTupleBuilder {
-    path: PathBuf,
+    file: FileId,
     errors: Vec<Error>,
 }
 
 impl DiagnosticTupleBuilder {
-    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+    pub fn new(file: FileId) -> Self {
         Self {
-            path: path.as_ref().to_path_buf(),
+            file,
             errors: Vec::new(),
         }
     }
@@ -41,64 +77,237 @@ impl DiagnosticTupleBuilder {
     }
 
     pub fn build(self) -> DiagnosticTuple {
-        let src = fs::read_to_string(&self.path).unwrap();
-        let errors = Self::wrap_diagnostics(&self.path, &src, self.errors);
         DiagnosticTuple {
-            path: self.path,
-            errors,
+            file: self.file,
+            errors: self.errors,
         }
     }
-
-    pub fn wrap_diagnostics(
-        path: &Path,
-        source_text: &str,
-        diagnostics: Vec<impl Into<Error>>,
-    ) -> Vec<Error> {
-        let source = Arc::new(NamedSource::new(
-            path.to_string_lossy(),
-            source_text.to_owned(),
-        ));
-
-        diagnostics
-            .into_iter()
-            .map(|diagnostic| diagnostic.into().with_source_code(Arc::clone(&source)))
-            .collect()
-    }
 }
 
 pub struct Reporter {
-    handler: GraphicalReportHandler,
+    renderer: Renderer,
+    sources: SourceDb,
+    levels: LintLevels,
     diagnostics: Vec<DiagnosticTuple>,
 }
 
+/// How [`Reporter::string`] renders diagnostics for humans
+enum Renderer {
+    Miette(Box<GraphicalReportHandler>),
+    Ariadne(ariadne::Config),
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Reporter {
     pub fn new() -> Self {
+        Self::themed(GraphicalTheme::unicode_nocolor())
+    }
+
+    pub fn themed(theme: GraphicalTheme) -> Self {
+        Self::with_renderer(Renderer::Miette(Box::new(
+            GraphicalReportHandler::new_themed(theme),
+        )))
+    }
+
+    /// Render diagnostics with ariadne instead of miette, which lays out
+    /// multiple labels of a diagnostic differently
+    pub fn ariadne(config: ariadne::Config) -> Self {
+        // spans are byte offsets, which ariadne does not assume by default
+        let config = config.with_index_type(IndexType::Byte);
+        Self::with_renderer(Renderer::Ariadne(config))
+    }
+
+    fn with_renderer(renderer: Renderer) -> Self {
         Self {
-            handler: GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor()),
+            renderer,
+            sources: SourceDb::new(),
+            levels: LintLevels::new(),
             diagnostics: Vec::new(),
         }
     }
 
-    pub fn report(&mut self, err: DiagnosticTuple) {
-        self.diagnostics.push(err);
+    /// Use `sources` to render diagnostics
+    pub fn with_sources(mut self, sources: SourceDb) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Report lints at `levels` instead of their default level
+    pub fn with_levels(mut self, levels: LintLevels) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    pub fn sources(&self) -> &SourceDb {
+        &self.sources
+    }
+
+    pub fn sources_mut(&mut self) -> &mut SourceDb {
+        &mut self.sources
+    }
+
+    /// Report diagnostics of a source registered in [`Reporter::sources`],
+    /// dropping allowed lints
+    pub fn report(&mut self, diagnostic: DiagnosticTuple) {
+        let file = self.sources.get(diagnostic.file);
+        let source = Arc::new(file.named_source());
+        let errors = diagnostic
+            .errors
+            .into_iter()
+            .filter_map(|error| self.levels.apply(error))
+            .map(|error| {
+                let at_end = (error.labels().into_iter().flatten())
+                    .any(|label| label.offset() >= file.text().len());
+                match at_end {
+                    true => error.with_source_code(file.padded_source()),
+                    false => error.with_source_code(Arc::clone(&source)),
+                }
+            })
+            .collect();
+        self.diagnostics.push(DiagnosticTuple {
+            file: diagnostic.file,
+            errors,
+        });
+    }
+
+    /// Number of reported diagnostics of `severity`, diagnostics without a
+    /// severity are errors
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .flat_map(|diagnostic| &diagnostic.errors)
+            .filter(|error| error.severity().unwrap_or(Severity::Error) == severity)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// A line such as `2 errors, 1 warning emitted`, or `None` if nothing
+    /// has been reported
+    pub fn summary(&self) -> Option<String> {
+        let counts = [
+            (Severity::Error, "error"),
+            (Severity::Warning, "warning"),
+            (Severity::Advice, "note"),
+        ]
+        .into_iter()
+        .filter_map(|(severity, name)| match self.count(severity) {
+            0 => None,
+            1 => Some(format!("1 {name}")),
+            n => Some(format!("{n} {name}s")),
+        })
+        .collect::<Vec<_>>();
+        match counts.is_empty() {
+            true => None,
+            false => Some(format!("{} emitted", counts.join(", "))),
+        }
     }
 
     pub fn string(&self) -> String {
+        match &self.renderer {
+            Renderer::Miette(handler) => self.miette(handler),
+            Renderer::Ariadne(config) => self.ariadne_string(*config),
+        }
+    }
+
+    fn miette(&self, handler: &GraphicalReportHandler) -> String {
         let mut err = String::new();
         for diagnostic in &self.diagnostics {
             for error in &diagnostic.errors {
-                self.handler
-                    .render_report(&mut err, error.as_ref())
-                    .unwrap();
+                handler.render_report(&mut err, error.as_ref()).unwrap();
                 err.write_str("\n").unwrap();
             }
         }
         err
     }
+
+    fn ariadne_string(&self, config: ariadne::Config) -> String {
+        let mut cache = AriadneSources::new(&self.sources);
+        let mut err = Vec::new();
+        for diagnostic in &self.diagnostics {
+            for error in &diagnostic.errors {
+                Self::ariadne_report(diagnostic.file, error, config)
+                    .write(&mut cache, &mut err)
+                    .unwrap();
+                err.push(b'\n');
+            }
+        }
+        String::from_utf8(err).unwrap()
+    }
+
+    fn ariadne_report<'a>(
+        file: FileId,
+        error: &Error,
+        config: ariadne::Config,
+    ) -> Report<'a, (FileId, Range<usize>)> {
+        let kind = match error.severity().unwrap_or(Severity::Error) {
+            Severity::Error => ReportKind::Error,
+            Severity::Warning => ReportKind::Warning,
+            Severity::Advice => ReportKind::Advice,
+        };
+        let labels = error.labels().into_iter().flatten().collect::<Vec<_>>();
+        let offset = labels.first().map_or(0, |label| label.offset());
+
+        let mut report = Report::build(kind, file, offset)
+            .with_config(config)
+            .with_message(error);
+        if let Some(code) = error.code() {
+            report = report.with_code(code);
+        }
+        if let Some(help) = error.help() {
+            report = report.with_help(help);
+        }
+        let mut colors = ColorGenerator::new();
+        for (order, label) in labels.into_iter().enumerate() {
+            let span = label.offset()..label.offset() + label.len();
+            let mut ariadne = Label::new((file, span))
+                .with_order(order as i32)
+                .with_color(colors.next());
+            if let Some(message) = label.label() {
+                ariadne = ariadne.with_message(message);
+            }
+            report = report.with_label(ariadne);
+        }
+        report.finish()
+    }
+
+    fn json_diagnostics(&self) -> impl Iterator<Item = JsonDiagnostic<'_>> {
+        self.diagnostics.iter().flat_map(|diagnostic| {
+            let file = self.sources.get(diagnostic.file);
+            diagnostic
+                .errors
+                .iter()
+                .map(move |error| JsonDiagnostic::new(file, error.as_ref()))
+        })
+    }
+
+    /// Render every diagnostic as a JSON object on its own line
+    pub fn json(&self) -> String {
+        let mut out = String::new();
+        for json in self.json_diagnostics() {
+            out.push_str(&serde_json::to_string(&json).unwrap());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Render every diagnostic as a SARIF log produced by the tool `name`
+    pub fn sarif(&self, name: &str, version: &str) -> String {
+        let log = SarifLog::new(name, version, self.json_diagnostics());
+        serde_json::to_string_pretty(&log).unwrap()
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::lint::{Level, LintLevels};
     use crate::reporter::{DiagnosticTuple, Reporter};
     use crate::span::Span;
     use miette::Diagnostic;
@@ -110,16 +319,133 @@ mod test {
         #[error("This is an example error")]
         #[diagnostic(help("This is an example help msg"))]
         Bar(#[label("Remove this space")] Span),
+        #[error("This is an example error with a code")]
+        #[diagnostic(code(example::baz))]
+        Baz {
+            #[label("Defined here")]
+            span: Span,
+            #[label("And used here")]
+            related: Span,
+        },
+        #[error("This is an example warning")]
+        #[diagnostic(code(MH0017), severity(Warning))]
+        Unused(#[label("Never used")] Span),
     }
 
-    unittest!(simple_err, |path, _| {
+    unittest!(simple_err, |path, src| {
         let err = Foo::Bar(Span { start: 5, end: 5 });
 
+        let mut reporter = Reporter::new();
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+
         let errors = vec![err.clone()];
-        let diagnostic: DiagnosticTuple = (path, errors).into();
+        let diagnostic: DiagnosticTuple = (file, errors).into();
+        reporter.report(diagnostic);
+
+        let result = reporter.string();
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(json, |path, src| {
+        let err = Foo::Bar(Span { start: 6, end: 11 });
 
         let mut reporter = Reporter::new();
-        reporter.report(diagnostic);
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        reporter.report((file, [err.clone(), err]).into());
+
+        let result = reporter.json();
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(sarif, |path, src| {
+        let mut reporter = Reporter::new();
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        reporter.report((file, [Foo::Bar(Span { start: 6, end: 11 })]).into());
+        let baz = Foo::Baz {
+            span: Span { start: 0, end: 3 },
+            related: Span { start: 11, end: 16 },
+        };
+        reporter.report((file, [baz]).into());
+        reporter.report((file, [Foo::Unused(Span { start: 0, end: 3 })]).into());
+
+        let result = reporter.sarif("mhc", "0.1.0");
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(lint_levels, |path, src| {
+        let diagnostics = || {
+            [
+                Foo::Unused(Span { start: 0, end: 3 }),
+                Foo::Unused(Span { start: 4, end: 6 }),
+                Foo::Bar(Span { start: 6, end: 11 }),
+            ]
+        };
+
+        let mut reporter = Reporter::new();
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        reporter.report((file, diagnostics()).into());
+        assert_eq!(
+            reporter.summary().as_deref(),
+            Some("1 error, 2 warnings emitted")
+        );
+
+        let mut levels = LintLevels::new();
+        levels.set("unused_parameters", Level::Allow).unwrap();
+        let mut reporter = Reporter::new().with_levels(levels);
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        reporter.report((file, diagnostics()).into());
+        assert_eq!(reporter.summary().as_deref(), Some("1 error emitted"));
+
+        let mut levels = LintLevels::new();
+        levels.deny_warnings(true);
+        let mut reporter = Reporter::new().with_levels(levels);
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        reporter.report((file, diagnostics()).into());
+        assert_eq!(reporter.summary().as_deref(), Some("3 errors emitted"));
+
+        insta::assert_snapshot!(reporter.string());
+    });
+
+    unittest!(ariadne, |path, src| {
+        let baz = Foo::Baz {
+            span: Span { start: 0, end: 3 },
+            related: Span { start: 11, end: 16 },
+        };
+        let config = ariadne::Config::default().with_color(false);
+
+        let mut reporter = Reporter::ariadne(config);
+        let file = reporter.sources_mut().add(path.to_string_lossy(), src);
+        let generated = reporter.sources_mut().add("<generated>", "nat x = 1;");
+        reporter.report((file, [Foo::Bar(Span { start: 6, end: 11 })]).into());
+        reporter.report((file, [baz]).into());
+        reporter.report((generated, [Foo::Unused(Span { start: 4, end: 5 })]).into());
+
+        let result = reporter.string();
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(same_name, |_, src| {
+        let config = ariadne::Config::default().with_color(false);
+
+        let mut reporter = Reporter::ariadne(config);
+        let first = reporter.sources_mut().add("<stdin>", src);
+        let second = reporter.sources_mut().add("<stdin>", "nat x = 1;");
+        reporter.report((first, [Foo::Unused(Span { start: 5, end: 6 })]).into());
+        reporter.report((second, [Foo::Unused(Span { start: 4, end: 5 })]).into());
+
+        let result = reporter.string();
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(multiple_sources, |_, src| {
+        let err = Foo::Bar(Span { start: 5, end: 5 });
+
+        let mut reporter = Reporter::new();
+        let stdin = reporter.sources_mut().add("<stdin>", src);
+        let generated = reporter.sources_mut().add("<generated>", "nat x = 1;");
+
+        reporter.report((stdin, [err.clone()]).into());
+        reporter.report((generated, [err]).into());
 
         let result = reporter.string();
         insta::assert_snapshot!(result);
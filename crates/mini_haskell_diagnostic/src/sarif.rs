@@ -0,0 +1,253 @@
+//! Rendering of diagnostics as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log.
+//!
+//! Every diagnostic code becomes a rule of the tool and every diagnostic a
+//! result, located by its labels. Columns are counted in UTF-16 code units,
+//! which is the SARIF default.
+
+use crate::codes;
+use crate::json::{JsonDiagnostic, JsonLabel};
+use crate::source::STDIN;
+use serde::Serialize;
+use std::path::Path;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Diagnostics without a code are reported under this rule
+const UNKNOWN_RULE: &str = "unknown";
+
+/// Location of [`STDIN`], which has no path
+const STDIN_URI: &str = "file:///dev/stdin";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: String,
+    pub version: String,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: String,
+    pub short_description: Message,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: Message,
+    pub locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+}
+
+impl SarifLog {
+    /// A log of a single run of the tool `name` reporting `diagnostics`
+    pub fn new<'src>(
+        name: &str,
+        version: &str,
+        diagnostics: impl IntoIterator<Item = JsonDiagnostic<'src>>,
+    ) -> Self {
+        let mut rules: Vec<Rule> = Vec::new();
+        let results = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let rule_id = diagnostic.code.as_deref().unwrap_or(UNKNOWN_RULE);
+                let rule_index = match rules.iter().position(|rule| rule.id == rule_id) {
+                    Some(index) => index,
+                    None => {
+                        rules.push(Rule {
+                            id: rule_id.to_string(),
+                            // codes outside the registry only have their message
+                            short_description: Message {
+                                text: codes::summary(rule_id)
+                                    .unwrap_or_else(|| diagnostic.message.clone()),
+                            },
+                        });
+                        rules.len() - 1
+                    }
+                };
+                SarifResult::new(diagnostic, rule_index, rules[rule_index].id.clone())
+            })
+            .collect();
+
+        SarifLog {
+            schema: SCHEMA,
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: name.to_string(),
+                        version: version.to_string(),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+impl SarifResult {
+    fn new(diagnostic: JsonDiagnostic, rule_index: usize, rule_id: String) -> Self {
+        let file = uri(diagnostic.file);
+        let mut labels = diagnostic.labels.into_iter();
+        let location = |label: JsonLabel, id| Location {
+            id,
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri: file.clone() },
+                region: Some(Region {
+                    start_line: label.start.line,
+                    start_column: label.start.column,
+                    end_line: label.end.line,
+                    end_column: label.end.column,
+                    byte_offset: label.byte_start,
+                    byte_length: label.byte_end - label.byte_start,
+                }),
+            },
+            message: label.message.map(|text| Message { text }),
+        };
+
+        // a result is always located in a file, even without any label
+        let primary = match labels.next() {
+            Some(label) => location(label, None),
+            None => Location {
+                id: None,
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation { uri: file.clone() },
+                    region: None,
+                },
+                message: None,
+            },
+        };
+        let related_locations = labels
+            .enumerate()
+            .map(|(id, label)| location(label, Some(id)))
+            .collect();
+
+        let mut text = diagnostic.message;
+        if let Some(help) = diagnostic.help {
+            text = format!("{text}\n{help}");
+        }
+
+        SarifResult {
+            rule_id,
+            rule_index,
+            level: match diagnostic.severity {
+                "advice" => "note",
+                level => level,
+            },
+            message: Message { text },
+            locations: vec![primary],
+            related_locations,
+        }
+    }
+}
+
+/// The URI reference of the source named `name`: a `file` URI for absolute
+/// paths and a relative reference otherwise
+fn uri(name: &str) -> String {
+    if name == STDIN {
+        return STDIN_URI.to_string();
+    }
+    let path = name.replace('\\', "/");
+    let absolute = Path::new(name).is_absolute();
+    let mut uri = match (absolute, path.starts_with('/')) {
+        (true, true) => "file://".to_string(),
+        // a Windows path starting with a drive letter
+        (true, false) => "file:///".to_string(),
+        (false, _) => String::new(),
+    };
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            // a colon in the first segment of a relative reference reads as a scheme
+            b':' if absolute => uri.push(':'),
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sarif::uri;
+    use crate::source::STDIN;
+
+    #[test]
+    fn uris() {
+        assert_eq!(uri("src/main.hs"), "src/main.hs");
+        assert_eq!(uri("my file:1.hs"), "my%20file%3A1.hs");
+        assert_eq!(uri("caf\u{e9}.hs"), "caf%C3%A9.hs");
+        assert_eq!(uri("/tmp/a b.hs"), "file:///tmp/a%20b.hs");
+        assert_eq!(uri("<generated>"), "%3Cgenerated%3E");
+        assert_eq!(uri(STDIN), "file:///dev/stdin");
+    }
+}
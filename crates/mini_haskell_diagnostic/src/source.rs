@@ -0,0 +1,85 @@
+use crate::span::LineIndex;
+use miette::NamedSource;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Name of a source read from standard input
+pub const STDIN: &str = "<stdin>";
+
+/// Handle of a source registered in a [`SourceDb`]
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(usize);
+
+#[derive(Debug)]
+pub struct SourceFile {
+    name: String,
+    text: Arc<str>,
+    line_index: LineIndex,
+}
+
+impl SourceFile {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+
+    pub(crate) fn named_source(&self) -> NamedSource {
+        NamedSource::new(&self.name, Arc::clone(&self.text))
+    }
+
+    /// The source with a space appended, miette does not render labels past
+    /// the last character so those at the end of the file point at it
+    pub(crate) fn padded_source(&self) -> NamedSource {
+        NamedSource::new(&self.name, format!("{} ", self.text))
+    }
+}
+
+/// In-memory store of every source diagnostics can be rendered against, be it
+/// a file on disk, stdin, an editor buffer or generated code.
+#[derive(Debug, Default)]
+pub struct SourceDb {
+    files: Vec<SourceFile>,
+}
+
+impl SourceDb {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Register `text` under a display `name`
+    pub fn add(&mut self, name: impl Into<String>, text: impl Into<Arc<str>>) -> FileId {
+        let text = text.into();
+        self.files.push(SourceFile {
+            name: name.into(),
+            line_index: LineIndex::new(&text),
+            text,
+        });
+        FileId(self.files.len() - 1)
+    }
+
+    /// Read and register the file at `path`
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> io::Result<FileId> {
+        let text = fs::read_to_string(path.as_ref())?;
+        Ok(self.add(path.as_ref().to_string_lossy(), text))
+    }
+
+    pub fn get(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(id, file)| (FileId(id), file))
+    }
+}
@@ -1,4 +1,6 @@
 use ariadne::Label;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq, Clone, Copy, Eq)]
 pub struct Span {
@@ -26,3 +28,179 @@ impl From<Span> for miette::SourceSpan {
         (val.start, val.end - val.start).into()
     }
 }
+
+/// Zero-based line and column, the unit of the column depends on the
+/// [`Encoding`] it was computed for.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Default)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Display for LineCol {
+    /// Render as the one-based `line:col` humans expect
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.col + 1)
+    }
+}
+
+/// Unit in which columns are counted
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Encoding {
+    /// Bytes, what [`Span`] uses
+    Utf8,
+    /// UTF-16 code units, what most editors and LSP clients use
+    Utf16,
+    /// Characters, what the rendered diagnostics show
+    Utf32,
+}
+
+/// A non-ASCII character, in UTF-8 columns of its line
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+struct WideChar {
+    start: usize,
+    end: usize,
+}
+
+impl WideChar {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn wide_len(&self, enc: Encoding) -> usize {
+        match enc {
+            Encoding::Utf8 => self.len(),
+            Encoding::Utf16 if self.len() == 4 => 2,
+            Encoding::Utf16 | Encoding::Utf32 => 1,
+        }
+    }
+}
+
+/// Maps byte offsets of a source file to lines and columns and back, without
+/// re-scanning the file for every lookup.
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct LineIndex {
+    // byte offset at which every line starts
+    line_starts: Vec<usize>,
+    // non-ASCII characters of every line that has some
+    wide_chars: HashMap<usize, Vec<WideChar>>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars: HashMap<usize, Vec<WideChar>> = HashMap::new();
+        for (offset, c) in text.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                let line = line_starts.len() - 1;
+                let start = offset - line_starts[line];
+                wide_chars.entry(line).or_default().push(WideChar {
+                    start,
+                    end: start + c.len_utf8(),
+                });
+            }
+        }
+        LineIndex {
+            line_starts,
+            wide_chars,
+            len: text.len(),
+        }
+    }
+
+    /// Number of lines, a trailing newline starts an empty last line
+    pub fn lines(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Line and UTF-8 column of a byte offset, `None` if it is out of bounds
+    pub fn line_col(&self, offset: usize) -> Option<LineCol> {
+        if offset > self.len {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        Some(LineCol {
+            line,
+            col: offset - self.line_starts[line],
+        })
+    }
+
+    /// Byte offset of a line and UTF-8 column, `None` if it is out of bounds
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        let start = *self.line_starts.get(line_col.line)?;
+        let end = self
+            .line_starts
+            .get(line_col.line + 1)
+            .map_or(self.len, |next| next - 1);
+        let offset = start + line_col.col;
+        (offset <= end).then_some(offset)
+    }
+
+    /// Convert a UTF-8 column into a column of `enc`
+    pub fn to_wide(&self, enc: Encoding, line_col: LineCol) -> LineCol {
+        let mut col = line_col.col;
+        for c in self.line_wide_chars(line_col.line) {
+            if c.end > line_col.col {
+                break;
+            }
+            col -= c.len() - c.wide_len(enc);
+        }
+        LineCol {
+            line: line_col.line,
+            col,
+        }
+    }
+
+    /// Convert a column of `enc` into a UTF-8 column
+    pub fn to_utf8(&self, enc: Encoding, line_col: LineCol) -> LineCol {
+        let mut col = line_col.col;
+        for c in self.line_wide_chars(line_col.line) {
+            if col <= c.start {
+                break;
+            }
+            col += c.len() - c.wide_len(enc);
+        }
+        LineCol {
+            line: line_col.line,
+            col,
+        }
+    }
+
+    /// Start and end of `span` with columns counted in `enc`
+    pub fn span(&self, enc: Encoding, span: Span) -> Option<(LineCol, LineCol)> {
+        let start = self.to_wide(enc, self.line_col(span.start)?);
+        let end = self.to_wide(enc, self.line_col(span.end)?);
+        Some((start, end))
+    }
+
+    fn line_wide_chars(&self, line: usize) -> &[WideChar] {
+        self.wide_chars.get(&line).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::span::{Encoding, LineCol, LineIndex};
+    use testsuite::unittest;
+
+    unittest!(line_index, |_, src| {
+        let index = LineIndex::new(src);
+        let positions = src
+            .char_indices()
+            .map(|(offset, c)| {
+                let utf8 = index.line_col(offset).unwrap();
+                let utf16 = index.to_wide(Encoding::Utf16, utf8);
+                let utf32 = index.to_wide(Encoding::Utf32, utf8);
+                assert_eq!(index.to_utf8(Encoding::Utf16, utf16), utf8);
+                assert_eq!(index.to_utf8(Encoding::Utf32, utf32), utf8);
+                assert_eq!(index.offset(utf8), Some(offset));
+                format!("{c:?} @ {offset}: utf8 {utf8}, utf16 {utf16}, utf32 {utf32}")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(index.line_col(src.len() + 1), None);
+        assert_eq!(index.offset(LineCol { line: 0, col: 100 }), None);
+        insta::assert_debug_snapshot!(positions);
+    });
+}
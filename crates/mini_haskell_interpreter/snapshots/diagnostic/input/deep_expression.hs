@@ -0,0 +1,2 @@
+func f (n : nat) -> nat = 1 + (1 + (1 + (1 + (1 + (1 + (1 + (1 + (1 + (1 + (1 + (1 + f (n + 1))))))))))));
+nat x = f 0;
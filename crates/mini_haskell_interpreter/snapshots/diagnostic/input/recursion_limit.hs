@@ -0,0 +1,2 @@
+func loop (n : nat) -> nat = loop (n + 1);
+nat forever = loop 0;
\ No newline at end of file
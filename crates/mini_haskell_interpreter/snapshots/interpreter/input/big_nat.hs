@@ -0,0 +1,4 @@
+nat max = 18446744073709551615;
+nat big = 1 + max;
+nat huge = 99999999999999999999999 + 99999999999999999999999;
+bool less = max < big;
\ No newline at end of file
@@ -0,0 +1,12 @@
+func parity (i : nat) (n : nat) (even : bool) -> bool = i = n ? even : parity (i + 1) n (even = F);
+func even (n : nat) -> bool = parity 0 n T;
+func twice (f : nat -> nat) (n : nat) -> nat = f (f n);
+func add (a : nat) (b : nat) -> nat = a + b;
+func sum (n : nat) (to : nat) (acc : nat) -> nat = n < to ? sum (n + 1) to (acc + n) : acc;
+func answer -> nat = 42;
+nat four = twice (add 1) 2;
+bool check = four < 5 = T & F = F;
+nat total = sum 0 100 0;
+nat picked = (even 3 ? add 1 : twice (add 10)) answer;
+bool done = even four;
+nat deep = sum 0 300 0;
\ No newline at end of file
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod test {
+    use crate::interpreter::{Interpreter, RECURSION_LIMIT, STACK_PER_LEVEL};
+    use mini_haskell_diagnostic::diagnostic::diagnostic;
+    use mini_haskell_parser::parser::Parser;
+    use std::thread;
+    use testsuite::unittest;
+
+    unittest!(recursion_limit, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            // the default limit needs more stack than a test thread has
+            Interpreter::new()
+                .with_recursion_limit(64)
+                .program(&program)
+                .err()
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(deep_expression, |path, src| {
+        // every nested expression counts towards the limit, not only calls
+        let result = thread::scope(|scope| {
+            thread::Builder::new()
+                .stack_size(RECURSION_LIMIT * STACK_PER_LEVEL)
+                .spawn_scoped(scope, || {
+                    diagnostic(path, src, |src| {
+                        let (program, _) = Parser::parse(src);
+                        Interpreter::run(&program).err()
+                    })
+                })
+                .unwrap()
+                .join()
+                .unwrap()
+        });
+
+        insta::assert_snapshot!(result);
+    });
+}
@@ -0,0 +1,28 @@
+use miette::Diagnostic;
+use mini_haskell_diagnostic::span::Span;
+use mini_haskell_parser::ast::Symbol;
+use thiserror::Error;
+
+#[derive(Diagnostic, Error, Debug, Eq, PartialEq, Clone)]
+pub enum RuntimeError {
+    #[error("RuntimeError: Recursion limit exceeded")]
+    #[diagnostic(code(MH0014))]
+    RecursionLimit {
+        #[label = "Evaluated more than {limit} levels deep"]
+        span: Span,
+        limit: usize,
+    },
+    #[error("RuntimeError: Uninitialized variable")]
+    #[diagnostic(code(MH0015))]
+    Uninitialized {
+        #[label = "`{name}` is used before it is initialized"]
+        span: Span,
+        name: Symbol,
+    },
+    #[error("RuntimeError: Ill-typed expression")]
+    #[diagnostic(code(MH0016), help("Run the type checker before evaluating a program"))]
+    IllTyped {
+        #[label = "This expression can not be evaluated"]
+        span: Span,
+    },
+}
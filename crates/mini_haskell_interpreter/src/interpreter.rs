@@ -0,0 +1,216 @@
+use crate::error::RuntimeError;
+use crate::value::{Closure, Value};
+use mini_haskell_diagnostic::span::Span;
+use mini_haskell_parser::ast::{
+    BinaryExpr, BinaryOp, CallExpr, CondExpr, DeclKind, Expr, ExprKind, FuncDecl, Ident, Literal,
+    Program, Symbol,
+};
+use std::collections::HashMap;
+
+/// Default maximum depth of nested expressions before evaluation is aborted,
+/// the body of a called function nesting inside the call. Every level takes
+/// native stack, so raising the limit requires a thread with a larger stack.
+pub const RECURSION_LIMIT: usize = 10_000;
+
+/// Native stack taken by a level of evaluation at most, a thread evaluating
+/// with a recursion limit of `limit` needs `limit * STACK_PER_LEVEL` bytes
+pub const STACK_PER_LEVEL: usize = 4 * 1024;
+
+type Env<'ast> = HashMap<Symbol, Value<'ast>>;
+
+pub struct Interpreter<'ast> {
+    funcs: HashMap<Symbol, &'ast FuncDecl>,
+    globals: Env<'ast>,
+    depth: usize,
+    recursion_limit: usize,
+    /// The innermost call being evaluated
+    call: Option<Span>,
+}
+
+impl<'ast> Interpreter<'ast> {
+    pub fn new() -> Self {
+        Self {
+            funcs: HashMap::new(),
+            globals: HashMap::new(),
+            depth: 0,
+            recursion_limit: RECURSION_LIMIT,
+            call: None,
+        }
+    }
+
+    /// Abort evaluation `limit` levels deep instead of [`RECURSION_LIMIT`]
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Evaluate every variable declaration of a type checked `program` in order,
+    /// returning the name and value of each of them.
+    pub fn run(program: &'ast Program) -> Result<Vec<(Symbol, Value<'ast>)>, RuntimeError> {
+        let mut interpreter = Interpreter::new();
+        interpreter.program(program)
+    }
+
+    pub fn program(
+        &mut self,
+        program: &'ast Program,
+    ) -> Result<Vec<(Symbol, Value<'ast>)>, RuntimeError> {
+        for decl in &program.decls {
+            if let DeclKind::Func(func) = &decl.kind {
+                self.funcs.insert(func.name.name.clone(), func);
+            }
+        }
+
+        let mut values = Vec::new();
+        for decl in &program.decls {
+            if let DeclKind::Var(var) = &decl.kind {
+                let value = self.eval(&var.init, &Env::new())?;
+                self.globals.insert(var.name.name.clone(), value.clone());
+                values.push((var.name.name.clone(), value));
+            }
+        }
+        Ok(values)
+    }
+
+    pub fn eval(&mut self, expr: &'ast Expr, env: &Env<'ast>) -> Result<Value<'ast>, RuntimeError> {
+        if self.depth >= self.recursion_limit {
+            return Err(RuntimeError::RecursionLimit {
+                span: self.call.unwrap_or(expr.span),
+                limit: self.recursion_limit,
+            });
+        }
+        self.depth += 1;
+        let result = match &expr.kind {
+            ExprKind::Literal(Literal::NatureNum(n)) => Ok(Value::Nat(n.clone())),
+            ExprKind::Literal(Literal::Bool(b)) => Ok(Value::Bool(*b)),
+            ExprKind::Var(ident) => self.var(ident, env),
+            ExprKind::Call(call) => self.call(call, expr.span, env),
+            ExprKind::Paren(expr) => self.eval(expr, env),
+            ExprKind::BinaryExpr(binary) => self.binary(binary, expr.span, env),
+            ExprKind::CondExpr(cond) => self.cond(cond, env),
+            ExprKind::Error => Err(RuntimeError::IllTyped { span: expr.span }),
+        };
+        self.depth -= 1;
+        result
+    }
+
+    fn var(&mut self, ident: &'ast Ident, env: &Env<'ast>) -> Result<Value<'ast>, RuntimeError> {
+        let name = &ident.name;
+        if let Some(value) = env.get(name).or_else(|| self.globals.get(name)) {
+            return Ok(value.clone());
+        }
+        match self.funcs.get(name) {
+            // a function without parameters denotes its result
+            Some(func) if func.params.is_empty() => self.invoke(func, Vec::new(), ident.span),
+            Some(func) => Ok(Value::Closure(Closure {
+                func,
+                args: Vec::new(),
+            })),
+            None => Err(RuntimeError::Uninitialized {
+                span: ident.span,
+                name: ident.name.clone(),
+            }),
+        }
+    }
+
+    fn call(
+        &mut self,
+        call: &'ast CallExpr,
+        span: Span,
+        env: &Env<'ast>,
+    ) -> Result<Value<'ast>, RuntimeError> {
+        let mut value = self.eval(&call.callee, env)?;
+        for arg in &call.args {
+            let Value::Closure(mut closure) = value else {
+                return Err(RuntimeError::IllTyped { span });
+            };
+            closure.args.push(self.eval(arg, env)?);
+            value = match closure.args.len() == closure.func.params.len() {
+                true => self.invoke(closure.func, closure.args, span)?,
+                false => Value::Closure(closure),
+            };
+        }
+        Ok(value)
+    }
+
+    fn invoke(
+        &mut self,
+        func: &'ast FuncDecl,
+        args: Vec<Value<'ast>>,
+        span: Span,
+    ) -> Result<Value<'ast>, RuntimeError> {
+        let env = func
+            .params
+            .iter()
+            .map(|param| param.name.name.clone())
+            .zip(args)
+            .collect::<Env>();
+        let outer = self.call.replace(span);
+        let result = self.eval(&func.body, &env);
+        self.call = outer;
+        result
+    }
+
+    fn binary(
+        &mut self,
+        binary: &'ast BinaryExpr,
+        span: Span,
+        env: &Env<'ast>,
+    ) -> Result<Value<'ast>, RuntimeError> {
+        let lhs = self.eval(&binary.lhs, env)?;
+        let rhs = self.eval(&binary.rhs, env)?;
+        match (binary.op, lhs, rhs) {
+            (BinaryOp::Plus, Value::Nat(lhs), Value::Nat(rhs)) => Ok(Value::Nat(lhs + rhs)),
+            (BinaryOp::Less, Value::Nat(lhs), Value::Nat(rhs)) => Ok(Value::Bool(lhs < rhs)),
+            (BinaryOp::Ampersand, Value::Bool(lhs), Value::Bool(rhs)) => {
+                Ok(Value::Bool(lhs && rhs))
+            }
+            (BinaryOp::Equal, Value::Nat(lhs), Value::Nat(rhs)) => Ok(Value::Bool(lhs == rhs)),
+            (BinaryOp::Equal, Value::Bool(lhs), Value::Bool(rhs)) => Ok(Value::Bool(lhs == rhs)),
+            _ => Err(RuntimeError::IllTyped { span }),
+        }
+    }
+
+    fn cond(&mut self, cond: &'ast CondExpr, env: &Env<'ast>) -> Result<Value<'ast>, RuntimeError> {
+        match self.eval(&cond.condition, env)? {
+            Value::Bool(true) => self.eval(&cond.then_branch, env),
+            Value::Bool(false) => self.eval(&cond.else_branch, env),
+            _ => Err(RuntimeError::IllTyped {
+                span: cond.condition.span,
+            }),
+        }
+    }
+}
+
+impl Default for Interpreter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::Interpreter;
+    use mini_haskell_parser::parser::Parser;
+    use testsuite::unittest;
+
+    unittest!(run, |_, src| {
+        let (program, _) = Parser::parse(src);
+        let values = Interpreter::run(&program)
+            .unwrap()
+            .into_iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(values);
+    });
+
+    unittest!(big_nat, |_, src| {
+        let (program, _) = Parser::parse(src);
+        let values = Interpreter::run(&program)
+            .unwrap()
+            .into_iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(values);
+    });
+}
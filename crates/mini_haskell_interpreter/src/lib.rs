@@ -0,0 +1,4 @@
+mod diagnostic;
+pub mod error;
+pub mod interpreter;
+pub mod value;
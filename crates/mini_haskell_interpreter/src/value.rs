@@ -0,0 +1,34 @@
+use mini_haskell_parser::ast::FuncDecl;
+use num_bigint::BigUint;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+pub enum Value<'ast> {
+    Nat(BigUint),
+    Bool(bool),
+    Closure(Closure<'ast>),
+}
+
+/// A (possibly partially applied) function
+#[derive(Debug, Clone)]
+pub struct Closure<'ast> {
+    pub func: &'ast FuncDecl,
+    pub args: Vec<Value<'ast>>,
+}
+
+impl Display for Value<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Nat(n) => write!(f, "{n}"),
+            Value::Bool(true) => f.write_str("T"),
+            Value::Bool(false) => f.write_str("F"),
+            Value::Closure(closure) => write!(
+                f,
+                "<func {} {}/{}>",
+                closure.func.name.name,
+                closure.args.len(),
+                closure.func.params.len()
+            ),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Compare tokenizing a source held in memory with the logos `SpannedIter`
+//! against streaming it in chunks.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mini_haskell_lexer::lexer::Tokenizer;
+use mini_haskell_lexer::stream::StreamTokenizer;
+
+const DECLS: &str = "\
+[ add two numbers [ of any size ] ]
+func add (x : nat) (y : nat) -> nat = x + y;
+nat big = 123456789012345678901234567890 + 1;
+bool small = big < 100 & T;
+func pick (b : bool) (x : nat) (y : nat) -> nat = b ? x : y;
+nat z = pick (z = 1) (add 1 2) 0;
+";
+
+/// A source of roughly `size` bytes
+fn source(size: usize) -> String {
+    DECLS.repeat(size / DECLS.len() + 1)
+}
+
+fn tokenizer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenizer");
+    for size in [64 * 1024, 16 * 1024 * 1024] {
+        let src = source(size);
+        group.throughput(Throughput::Bytes(src.len() as u64));
+        group.bench_with_input(BenchmarkId::new("spanned_iter", size), &src, |b, src| {
+            b.iter(|| Tokenizer::new(black_box(src)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("stream", size), &src, |b, src| {
+            b.iter(|| StreamTokenizer::new(black_box(src.as_bytes())).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = tokenizer
+}
+criterion_main!(benches);
@@ -0,0 +1,3 @@
+nat x = 1;
+[ increment [ by one ]
+func inc (n : nat) -> nat = n + 1;
\ No newline at end of file
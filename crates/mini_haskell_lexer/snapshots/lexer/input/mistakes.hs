@@ -0,0 +1,4 @@
+nat x = 007;
+nat café = 1 $ 2;
+bool b = true && false;
+bool c = x != 0 || x == 1;
\ No newline at end of file
@@ -0,0 +1,2 @@
+nat x = 1; [ outer [ inner ] still a comment ] nat y = 2;
+[] [[]] [ ] ] ]
\ No newline at end of file
@@ -0,0 +1,6 @@
+bool b = T & F;
+nat n = 1234567890123456789012345678901234567890 + 0;
+func f (x : nat) (y : bool) -> nat = y ? x : 1; [ outer [ inner ] still a comment ]
+nat x = 007 + café $ 2 -> 3 < 4;
+bool c = true && false != x == 1 || T;
+[ unterminated [ comment ] é
\ No newline at end of file
@@ -0,0 +1,7 @@
+[ header
+  comment ]
+nat x = 1;   [ trailing ]
+
+  func f (a : nat) -> nat = a $ 1; [ spans
+lines ] bool y = T;
+	 
@@ -5,8 +5,8 @@ mod test {
     use mini_haskell_diagnostic::diagnostic::diagnostic;
     use testsuite::unittest;
 
-    unittest!(invalid_token, |path, _| {
-        let result = diagnostic(path, |src| {
+    unittest!(invalid_token, |path, src| {
+        let result = diagnostic(path, src, |src| {
             Token::tokens(src)
                 .into_iter()
                 .filter(|tok| tok.is_err())
@@ -16,4 +16,26 @@ mod test {
 
         insta::assert_snapshot!(result);
     });
+
+    unittest!(unterminated_comment, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            Token::tokens(src)
+                .into_iter()
+                .filter_map(Result::err)
+                .collect::<Vec<LexingError>>()
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(mistakes, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            Token::tokens(src)
+                .into_iter()
+                .filter_map(Result::err)
+                .collect::<Vec<LexingError>>()
+        });
+
+        insta::assert_snapshot!(result);
+    });
 }
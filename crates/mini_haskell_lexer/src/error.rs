@@ -1,11 +1,148 @@
+use crate::lexer::TokenTy;
 use miette::Diagnostic;
+use mini_haskell_diagnostic::fix::Suggestion;
 use mini_haskell_diagnostic::span::Span;
+use std::io;
 use thiserror::Error;
 
 #[derive(Diagnostic, Error, Debug, PartialEq, Clone)]
 #[non_exhaustive]
 pub enum LexingError {
+    /// A stray character no token starts with
     #[error("Unexpected token")]
-    #[diagnostic()]
+    #[diagnostic(
+        code(MH0001),
+        help("The operators are `+`, `<`, `&`, `=`, `->` and `? :`")
+    )]
     UnexpectedToken(#[label = "Invalid token"] Span),
+    #[error("Unterminated comment")]
+    #[diagnostic(
+        code(MH0019),
+        help("Close every `[` with a matching `]`, comments can be nested")
+    )]
+    UnterminatedComment {
+        #[label = "This comment is never closed"]
+        open: Span,
+        #[label = "The file ends here"]
+        eof: Span,
+    },
+    #[error("Natural number with leading zeros")]
+    #[diagnostic(code(MH0020), help("Write `{value}` instead"))]
+    LeadingZeros {
+        #[label = "Leading zeros are not allowed"]
+        span: Span,
+        /// The literal without its leading zeros
+        value: String,
+    },
+    #[error("Identifier `{name}` contains non-ASCII characters")]
+    #[diagnostic(
+        code(MH0021),
+        help("Identifiers may only contain ASCII letters, digits and `_`")
+    )]
+    NonAsciiIdentifier {
+        #[label = "Non-ASCII identifier"]
+        span: Span,
+        name: String,
+    },
+    #[error("Unknown operator `{op}`")]
+    #[diagnostic(code(MH0022))]
+    UnknownOperator {
+        #[label = "Not a mini-haskell operator"]
+        span: Span,
+        op: &'static str,
+        #[help]
+        help: &'static str,
+    },
+    #[error("Boolean literals are written `T` and `F`")]
+    #[diagnostic(code(MH0023), help("Write `{}` instead", if *value { "T" } else { "F" }))]
+    BoolKeyword {
+        #[label = "Not a boolean literal"]
+        span: Span,
+        value: bool,
+    },
+}
+
+/// Error of a [`StreamTokenizer`](crate::stream::StreamTokenizer), which may
+/// also fail to read its source
+#[derive(Diagnostic, Error, Debug)]
+pub enum StreamError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Lexing(#[from] LexingError),
+    #[error("Failed to read the source: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Error of text matching no token, the [`Tokenizer`](crate::lexer::Tokenizer)
+/// fills in its span
+impl Default for LexingError {
+    fn default() -> Self {
+        LexingError::UnexpectedToken(Span { start: 0, end: 0 })
+    }
+}
+
+impl LexingError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexingError::UnexpectedToken(span)
+            | LexingError::LeadingZeros { span, .. }
+            | LexingError::NonAsciiIdentifier { span, .. }
+            | LexingError::UnknownOperator { span, .. }
+            | LexingError::BoolKeyword { span, .. } => *span,
+            LexingError::UnterminatedComment { open, eof } => Span {
+                start: open.start,
+                end: eof.end,
+            },
+        }
+    }
+
+    /// The same error with its spans moved `offset` bytes to the right, for
+    /// text lexed out of a larger source
+    pub(crate) fn shifted(mut self, offset: usize) -> Self {
+        let shift = |span: &mut Span| {
+            span.start += offset;
+            span.end += offset;
+        };
+        match &mut self {
+            LexingError::UnexpectedToken(span)
+            | LexingError::LeadingZeros { span, .. }
+            | LexingError::NonAsciiIdentifier { span, .. }
+            | LexingError::UnknownOperator { span, .. }
+            | LexingError::BoolKeyword { span, .. } => shift(span),
+            LexingError::UnterminatedComment { open, eof } => {
+                shift(open);
+                shift(eof);
+            }
+        }
+        self
+    }
+
+    /// The token a literal spelled wrong stands for, which a parser can go on
+    /// with after reporting the error
+    pub fn recovery(&self) -> Option<TokenTy> {
+        match self {
+            LexingError::LeadingZeros { .. } => Some(TokenTy::NatLit),
+            LexingError::BoolKeyword { value, .. } => Some(TokenTy::BoolLit(*value)),
+            _ => None,
+        }
+    }
+
+    /// The fix for mistakes with a single mini-haskell spelling
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self {
+            LexingError::UnknownOperator { span, op: "&&", .. } => Some(Suggestion {
+                span: *span,
+                replacement: "&",
+            }),
+            LexingError::UnknownOperator { span, op: "==", .. } => Some(Suggestion {
+                span: *span,
+                replacement: "=",
+            }),
+            LexingError::BoolKeyword { span, value } => Some(Suggestion {
+                span: *span,
+                replacement: if *value { "T" } else { "F" },
+            }),
+            _ => None,
+        }
+    }
 }
@@ -1,8 +1,14 @@
 use crate::error::LexingError;
-use logos::{Logos, SpannedIter};
+use crate::stream::StreamTokenizer;
+use crate::symbol::{Interner, Symbol};
+use crate::trivia::TriviaTokenizer;
+use logos::{Lexer, Logos, SpannedIter};
 use mini_haskell_diagnostic::span::Span;
+use std::io::Read;
 
-#[derive(Logos, Debug, PartialEq, Clone)]
+#[derive(Logos, Debug, PartialEq, Eq, Clone)]
+#[logos(error = LexingError)]
+#[logos(extras = Interner)]
 #[logos(skip r"[ \t\n\f]+")] // Ignore this regex pattern between tokens
 pub enum TokenTy {
     // The variable type Boolean, to declare a Boolean variable.
@@ -10,13 +16,17 @@ pub enum TokenTy {
     BoolDecl,
 
     // Boolean literal
-    #[token("T", | _ | false)]
-    #[token("F", | _ | true)]
+    #[token("T", | _ | true)]
+    #[token("F", | _ | false)]
+    #[token("true", bool_keyword)]
+    #[token("false", bool_keyword)]
     BoolLit(bool),
 
-    #[regex(r"(?:[1-9][0-9]*|0)", | lex | lex.slice().parse::< usize > ().unwrap())]
-    // Nature number literal
-    NatLit(usize),
+    #[regex(r"(?:[1-9][0-9]*|0)")]
+    #[regex(r"0[0-9]+", leading_zeros)]
+    // Nature number literal, of arbitrary precision, its digits are the text
+    // of the token
+    NatLit,
 
     // The variable type natural number, to declare a natural number.
     #[token("nat")]
@@ -44,6 +54,8 @@ pub enum TokenTy {
     Plus,
 
     #[token("&")]
+    #[token("&&", unknown_operator)]
+    #[token("||", unknown_operator)]
     Ampersand,
 
     #[token("->")]
@@ -53,26 +65,101 @@ pub enum TokenTy {
     Less,
 
     #[token("=")]
+    #[token("!=", unknown_operator)]
+    #[token("==", unknown_operator)]
     Equal,
 
-    #[regex(r"\[[^\]]*\]", | lex | lex.slice().to_owned())]
-    // see https://github.com/maciejhirsz/logos/issues/246
-    Comment(String),
+    // Comments nest, which a regex can not express
+    #[token("[", comment)]
+    Comment,
 
-    #[regex(r"[a-zA-z][a-zA-Z0-9_]*", | lex | lex.slice().to_owned())]
-    Identifier(String),
+    #[regex(r"[a-zA-Z][a-zA-Z0-9_]*", | lex | lex.extras.intern(lex.slice()))]
+    #[regex(
+        r"(?:[a-zA-Z][a-zA-Z0-9_]*)?[\p{Alphabetic}&&[^\x00-\x7F]][\p{Alphabetic}0-9_]*",
+        non_ascii_identifier
+    )]
+    Identifier(Symbol),
 
     EOF,
 }
 
+/// Reject `true` and `false`, spelled `T` and `F` in mini-haskell
+fn bool_keyword(lex: &mut Lexer<TokenTy>) -> Result<bool, LexingError> {
+    Err(LexingError::BoolKeyword {
+        span: lex.span().into(),
+        value: lex.slice() == "true",
+    })
+}
+
+fn leading_zeros(lex: &mut Lexer<TokenTy>) -> Result<(), LexingError> {
+    let value = lex.slice().trim_start_matches('0');
+    Err(LexingError::LeadingZeros {
+        span: lex.span().into(),
+        value: if value.is_empty() { "0" } else { value }.to_owned(),
+    })
+}
+
+fn non_ascii_identifier(lex: &mut Lexer<TokenTy>) -> Result<Symbol, LexingError> {
+    Err(LexingError::NonAsciiIdentifier {
+        span: lex.span().into(),
+        name: lex.slice().to_owned(),
+    })
+}
+
+/// Reject operators of other languages, pointing to the mini-haskell spelling
+fn unknown_operator(lex: &mut Lexer<TokenTy>) -> Result<(), LexingError> {
+    let (op, help) = match lex.slice() {
+        "&&" => ("&&", "Write `&` instead"),
+        "||" => ("||", "There is no `||`, write `a ? T : b` instead"),
+        "!=" => ("!=", "There is no `!=`, write `a = b ? F : T` instead"),
+        _ => ("==", "Write `=` instead"),
+    };
+    Err(LexingError::UnknownOperator {
+        span: lex.span().into(),
+        op,
+        help,
+    })
+}
+
+/// Lex the rest of a comment whose opening `[` has just been matched
+fn comment(lex: &mut Lexer<TokenTy>) -> Result<(), LexingError> {
+    let mut depth = 1usize;
+    for (i, c) in lex.remainder().char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 1 => {
+                lex.bump(i + 1);
+                return Ok(());
+            }
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    let start = lex.span().start;
+    lex.bump(lex.remainder().len());
+    let end = lex.span().end;
+    // the last character, an empty span past the end of the file is not rendered
+    let last = lex.slice().chars().next_back().map_or(0, char::len_utf8);
+    Err(LexingError::UnterminatedComment {
+        open: Span {
+            start,
+            end: start + 1,
+        },
+        eof: Span {
+            start: end - last,
+            end,
+        },
+    })
+}
+
 impl TokenTy {
     pub fn name(&self) -> &'static str {
         match self {
             TokenTy::BoolDecl => "bool",
             TokenTy::BoolLit(_) => "T/F",
-            TokenTy::NatLit(_) => "natural number",
+            TokenTy::NatLit => "natural number",
             TokenTy::Nat => "nat",
-            TokenTy::Func => "fun",
+            TokenTy::Func => "func",
             TokenTy::QuestionMark => "?",
             TokenTy::Colon => ":",
             TokenTy::Semicolon => ";",
@@ -83,7 +170,7 @@ impl TokenTy {
             TokenTy::Arrow => "->",
             TokenTy::Less => "<",
             TokenTy::Equal => "=",
-            TokenTy::Comment(_) => "comment",
+            TokenTy::Comment => "comment",
             TokenTy::Identifier(_) => "identifier",
             TokenTy::EOF => "EOF",
         }
@@ -112,6 +199,18 @@ impl<'src> Tokenizer<'src> {
             inner: TokenTy::lexer(source.as_ref()).spanned(),
         }
     }
+
+    /// Tokenize `source` without losing whitespace and comments, see
+    /// [`crate::trivia`]
+    pub fn with_trivia<S: AsRef<str> + ?Sized>(source: &'src S) -> TriviaTokenizer<'src> {
+        TriviaTokenizer::new(source)
+    }
+
+    /// Tokenize a source read in chunks, without holding all of it in memory,
+    /// see [`crate::stream`]
+    pub fn from_reader<R: Read>(reader: R) -> StreamTokenizer<R> {
+        StreamTokenizer::new(reader)
+    }
 }
 
 impl<'src> Iterator for Tokenizer<'src> {
@@ -120,7 +219,9 @@ impl<'src> Iterator for Tokenizer<'src> {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next().map(|(token, span)| match token {
             Ok(ty) => Ok(Token::new(ty, span.into())),
-            Err(_) => Err(LexingError::UnexpectedToken(span.into())),
+            // logos does not know the span of text matching no token
+            Err(LexingError::UnexpectedToken(_)) => Err(LexingError::UnexpectedToken(span.into())),
+            Err(err) => Err(err),
         })
     }
 }
@@ -145,4 +246,14 @@ mod tests {
         let tokens = Token::tokens(&src);
         insta::assert_debug_snapshot!(tokens);
     });
+
+    unittest!(nested_comment, |_, src| {
+        let tokens = Token::tokens(&src);
+        insta::assert_debug_snapshot!(tokens);
+    });
+
+    unittest!(mistakes, |_, src| {
+        let tokens = Token::tokens(&src);
+        insta::assert_debug_snapshot!(tokens);
+    });
 }
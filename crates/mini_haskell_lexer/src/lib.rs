@@ -1,3 +1,6 @@
 mod diagnostic;
 pub mod error;
 pub mod lexer;
+pub mod stream;
+pub mod symbol;
+pub mod trivia;
@@ -0,0 +1,312 @@
+//! Tokenization of a source read in chunks from any [`Read`], for inputs too
+//! large to hold in memory at once.
+//!
+//! Tokens are lexed out of a buffer holding the next chunk of the source and
+//! carry byte spans relative to the start of the whole source. A token ending
+//! at the end of the buffer might continue in the next chunk, so it is lexed
+//! again once its end has been read. Every prefix of a token is either a token
+//! itself or one character short of it, so a token followed by another
+//! character in the buffer is final. The end of a long token, a comment or a
+//! word, is searched for in each new chunk only, scanning every byte of the
+//! source a bounded number of times. The buffer holds a chunk plus the
+//! unfinished token, only a single token longer than a chunk, like a large
+//! comment, makes it grow. As the text is dropped once lexed, the text of
+//! literals and comments is yielded along with their tokens. Every batch of
+//! tokens is lexed with an interner of its own, no name outlives its symbols.
+
+use crate::error::StreamError;
+use crate::lexer::{Token, TokenTy, Tokenizer};
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read};
+use std::str;
+
+/// Number of bytes read from the source at once
+pub const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// Number of tokens lexed at once, few enough for them to stay in cache
+const BATCH: usize = 64;
+
+/// A token read from a stream, with its text if its type does not carry it
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lexeme {
+    pub token: Token,
+    /// The digits of a literal or the text of a comment
+    pub text: Option<String>,
+}
+
+pub struct StreamTokenizer<R> {
+    reader: R,
+    /// Source text read so far, starting at byte `offset` of the source
+    text: String,
+    /// Length of the prefix of `text` already lexed
+    lexed: usize,
+    offset: usize,
+    /// Bytes read but not decoded yet, an incomplete UTF-8 sequence between
+    /// chunks
+    chunk: Vec<u8>,
+    capacity: usize,
+    /// Tokens lexed out of `text` but not yielded yet
+    pending: VecDeque<Result<Lexeme, StreamError>>,
+    /// Whether the reader has reached the end of the source
+    eof: bool,
+    /// Whether the source is not valid UTF-8 past the end of `text`
+    invalid: bool,
+    /// Error to yield once `pending` is empty, ending the stream
+    error: Option<io::Error>,
+    /// The token at `lexed` reaching the end of `text`
+    unfinished: Option<Unfinished>,
+}
+
+/// Scan state of a token reaching the end of the text read so far
+enum Unfinished {
+    /// A comment, nested `depth` levels deep at byte `scanned` of the source
+    Comment { scanned: usize, depth: usize },
+    /// An identifier or a number, scanned up to byte `scanned` of the source
+    Word { scanned: usize },
+}
+
+impl Unfinished {
+    /// The scan state of the token starting at byte `start` of the source
+    /// with `text`, `None` for a token at most a few bytes long
+    fn new(text: &str, start: usize) -> Option<Self> {
+        match text.chars().next()? {
+            '[' => Some(Unfinished::Comment {
+                scanned: start,
+                depth: 0,
+            }),
+            c if is_word(c) => Some(Unfinished::Word { scanned: start }),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `c` can continue an identifier or a number
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl<R: Read> StreamTokenizer<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// A tokenizer reading `capacity` bytes of the source at once
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        assert!(
+            capacity > 0,
+            "the capacity of a StreamTokenizer must not be 0"
+        );
+        StreamTokenizer {
+            reader,
+            text: String::with_capacity(capacity),
+            lexed: 0,
+            offset: 0,
+            chunk: Vec::with_capacity(capacity),
+            capacity,
+            pending: VecDeque::with_capacity(BATCH),
+            eof: false,
+            invalid: false,
+            error: None,
+            unfinished: None,
+        }
+    }
+
+    /// Whether no more text will be read
+    fn finished(&self) -> bool {
+        self.eof || self.invalid
+    }
+
+    /// Continue scanning the unfinished token over the text read since,
+    /// returning whether its end has been read
+    fn resume(&mut self) -> bool {
+        let end = self.offset + self.text.len();
+        let ended = match &mut self.unfinished {
+            None => return true,
+            Some(Unfinished::Comment { scanned, depth }) => {
+                let rest = &self.text[*scanned - self.offset..];
+                // brackets are ASCII, they never occur inside another character
+                let ended = rest.bytes().any(|b| {
+                    match b {
+                        b'[' => *depth += 1,
+                        b']' => *depth -= 1,
+                        _ => return false,
+                    }
+                    *depth == 0
+                });
+                *scanned = end;
+                ended
+            }
+            Some(Unfinished::Word { scanned }) => {
+                let rest = &self.text[*scanned - self.offset..];
+                *scanned = end;
+                !rest.chars().all(is_word)
+            }
+        };
+        if ended {
+            self.unfinished = None;
+        }
+        ended
+    }
+
+    /// Lex the next final tokens of `text` into `pending`
+    fn lex(&mut self) {
+        if !self.finished() && !self.resume() {
+            return;
+        }
+        let text = &self.text[self.lexed..];
+        let offset = self.offset + self.lexed;
+        let last = self.finished();
+        let mut lexed = 0;
+        for token in Tokenizer::new(text).take(BATCH) {
+            let end = match &token {
+                Ok(token) => token.span.end,
+                Err(err) => err.span().end,
+            };
+            if !last && end == text.len() {
+                let start = match &token {
+                    Ok(token) => token.span.start,
+                    Err(err) => err.span().start,
+                };
+                self.unfinished = Unfinished::new(&text[start..], offset + start);
+                break;
+            }
+            self.pending.push_back(match token {
+                Ok(token) => {
+                    let text = match token.ty {
+                        TokenTy::NatLit | TokenTy::Comment => {
+                            Some(text[token.span.start..token.span.end].to_owned())
+                        }
+                        _ => None,
+                    };
+                    let mut span = token.span;
+                    span.start += offset;
+                    span.end += offset;
+                    Ok(Lexeme {
+                        token: Token::new(token.ty, span),
+                        text,
+                    })
+                }
+                Err(err) => Err(err.shifted(offset).into()),
+            });
+            lexed = end;
+        }
+        if last && self.pending.len() < BATCH {
+            // every token of the source has been lexed
+            lexed = text.len();
+            if self.invalid {
+                self.error = Some(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+            }
+        }
+        self.lexed += lexed;
+        // drop the whitespace before the next token instead of holding it until
+        // the token has been read
+        let rest = &self.text[self.lexed..];
+        self.lexed += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\x0C']).len();
+    }
+
+    /// Drop the lexed text and append the next chunk of the source
+    fn fill(&mut self) -> io::Result<()> {
+        self.text.drain(..self.lexed);
+        self.offset += self.lexed;
+        self.lexed = 0;
+
+        let len = self.chunk.len();
+        self.chunk.resize(len + self.capacity, 0);
+        let read = loop {
+            match self.reader.read(&mut self.chunk[len..]) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        self.chunk.truncate(len + *read.as_ref().unwrap_or(&0));
+        if read? == 0 {
+            self.eof = true;
+            self.invalid = !self.chunk.is_empty();
+            return Ok(());
+        }
+        match str::from_utf8(&self.chunk) {
+            Ok(text) => {
+                self.text.push_str(text);
+                self.chunk.clear();
+            }
+            Err(err) => {
+                let valid = err.valid_up_to();
+                self.text
+                    .push_str(str::from_utf8(&self.chunk[..valid]).unwrap());
+                // an incomplete sequence at the end of the chunk is completed
+                // by the next one, anything else is invalid
+                self.invalid = err.error_len().is_some();
+                self.chunk.drain(..valid);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for StreamTokenizer<R> {
+    type Item = Result<Lexeme, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Some(token);
+            }
+            if let Some(err) = self.error.take() {
+                return Some(Err(err.into()));
+            }
+            if self.finished() && self.lexed == self.text.len() {
+                return None;
+            }
+            self.lex();
+            // the text holds no final token yet
+            if self.pending.is_empty() && self.error.is_none() && !self.finished() {
+                if let Err(err) = self.fill() {
+                    // end the stream after the error
+                    self.eof = true;
+                    self.lexed = self.text.len();
+                    self.error = Some(err);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::StreamError;
+    use crate::lexer::{Token, TokenTy};
+    use crate::stream::{Lexeme, StreamTokenizer};
+    use testsuite::unittest;
+
+    unittest!(chunked, |_, src| {
+        let expected = Token::tokens(src);
+        for capacity in [1, 2, 3, 5, 8, 13, 1024] {
+            let tokens = StreamTokenizer::with_capacity(capacity, src.as_bytes())
+                .map(|lexeme| match lexeme {
+                    Ok(Lexeme { token, text }) => {
+                        let lexeme = &src[token.span.start..token.span.end];
+                        let carried = matches!(token.ty, TokenTy::NatLit | TokenTy::Comment);
+                        assert_eq!(text.as_deref(), carried.then_some(lexeme));
+                        Ok(token)
+                    }
+                    Err(StreamError::Lexing(err)) => Err(err),
+                    Err(StreamError::Io(err)) => panic!("{err}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(tokens, expected, "capacity {capacity}");
+        }
+
+        // whitespace is dropped as it is read instead of piling up
+        let blank = format!("{}{src}", " ".repeat(4096));
+        let mut tokens = StreamTokenizer::with_capacity(8, blank.as_bytes());
+        tokens.by_ref().for_each(drop);
+        assert!(tokens.text.capacity() < 4096);
+
+        let invalid = [src.as_bytes(), b"\xff"].concat();
+        let last = StreamTokenizer::with_capacity(7, invalid.as_slice()).last();
+        assert!(matches!(last, Some(Err(StreamError::Io(_)))));
+    });
+}
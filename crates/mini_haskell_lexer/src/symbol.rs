@@ -0,0 +1,98 @@
+//! Interned identifiers.
+//!
+//! The name of an identifier is stored once per [`Interner`] and shared by
+//! every [`Symbol`] of it, so cloning a symbol does not allocate and symbols
+//! of the same interner compare by address. Each [`Tokenizer`] owns its own
+//! interner, which is dropped along with it, a name lives as long as its
+//! symbols do. Only identifiers are interned, the text of comments and
+//! literals is the text of their tokens in the source.
+//!
+//! [`Tokenizer`]: crate::lexer::Tokenizer
+
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Handle to an interned string, equal symbols have equal strings
+#[derive(Clone)]
+pub struct Symbol(Arc<str>);
+
+/// The names interned so far, every name is allocated once
+#[derive(Default)]
+pub struct Interner {
+    names: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(interned) = self.names.get(name) {
+            return Symbol(interned.clone());
+        }
+        let interned: Arc<str> = name.into();
+        self.names.insert(interned.clone());
+        Symbol(interned)
+    }
+}
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Symbols of the same interner share their string, those of different
+/// interners are compared by content
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Formatted as the string itself
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+/// A symbol of its own, not shared with the names of any interner
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Symbol(name.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::symbol::{Interner, Symbol};
+
+    #[test]
+    fn intern() {
+        let mut interner = Interner::default();
+        let a = interner.intern("a");
+        assert_eq!(a, interner.intern("a"));
+        assert_eq!(a, Symbol::from("a"));
+        assert_ne!(a, interner.intern("b"));
+        assert_eq!(a.as_str(), "a");
+        assert_eq!(format!("{a} {a:?}"), "a \"a\"");
+        // a name is freed along with the interner and its symbols
+        let weak = std::sync::Arc::downgrade(&a.0);
+        drop((a, interner));
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,246 @@
+//! Lossless tokenization: every byte of the source belongs to exactly one
+//! token or to the trivia around it.
+//!
+//! Trivia following a token on the same line is attached to it as trailing
+//! trivia, any other trivia is attached to the next token as leading trivia.
+//! The trivia at the end of the source is attached to a final
+//! [`TokenTy::EOF`] token, so concatenating the [`TriviaToken::text`] of every
+//! token reproduces the source byte for byte.
+
+use crate::error::LexingError;
+use crate::lexer::{Token, TokenTy, Tokenizer};
+use mini_haskell_diagnostic::span::Span;
+use std::collections::VecDeque;
+use std::iter::Peekable;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TriviaKind {
+    Whitespace,
+    Comment,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+/// A token, or the invalid text the lexer could not make a token of, along
+/// with its surrounding trivia
+#[derive(Debug, PartialEq, Clone)]
+pub struct TriviaToken {
+    pub leading: Vec<Trivia>,
+    pub token: Result<Token, LexingError>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl TriviaToken {
+    /// Span of the token itself, without trivia
+    pub fn token_span(&self) -> Span {
+        match &self.token {
+            Ok(token) => token.span,
+            Err(err) => err.span(),
+        }
+    }
+
+    /// Span of the token including its trivia
+    pub fn span(&self) -> Span {
+        let token = self.token_span();
+        Span {
+            start: self
+                .leading
+                .first()
+                .map_or(token.start, |trivia| trivia.span.start),
+            end: self
+                .trailing
+                .last()
+                .map_or(token.end, |trivia| trivia.span.end),
+        }
+    }
+
+    /// Source text of the token including its trivia
+    pub fn text<'src>(&self, src: &'src str) -> &'src str {
+        let span = self.span();
+        &src[span.start..span.end]
+    }
+}
+
+/// A contiguous piece of the source
+enum Piece {
+    Trivia(Trivia),
+    Token(Result<Token, LexingError>),
+}
+
+/// Iterator over the pieces of a source, whitespace is split before its first
+/// newline so that trailing trivia never spans lines.
+struct Pieces<'src> {
+    src: &'src str,
+    tokenizer: Tokenizer<'src>,
+    // end of the pieces yielded so far
+    pos: usize,
+    queue: VecDeque<Piece>,
+}
+
+impl<'src> Pieces<'src> {
+    fn whitespace(&mut self, end: usize) {
+        let start = self.pos;
+        let split = self.src[start..end]
+            .find('\n')
+            .map_or(end, |newline| start + newline);
+        for span in [Span { start, end: split }, Span { start: split, end }] {
+            if span.start < span.end {
+                self.queue.push_back(Piece::Trivia(Trivia {
+                    kind: TriviaKind::Whitespace,
+                    span,
+                }));
+            }
+        }
+        self.pos = end;
+    }
+}
+
+impl Iterator for Pieces<'_> {
+    type Item = Piece;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(piece) = self.queue.pop_front() {
+            return Some(piece);
+        }
+        let Some(token) = self.tokenizer.next() else {
+            self.whitespace(self.src.len());
+            return self.queue.pop_front();
+        };
+        let span = match &token {
+            Ok(token) => token.span,
+            Err(err) => err.span(),
+        };
+        self.whitespace(span.start);
+        self.queue.push_back(match token {
+            Ok(Token {
+                ty: TokenTy::Comment,
+                span,
+            }) => Piece::Trivia(Trivia {
+                kind: TriviaKind::Comment,
+                span,
+            }),
+            token => Piece::Token(token),
+        });
+        self.pos = span.end;
+        self.queue.pop_front()
+    }
+}
+
+/// Tokenizer attaching trivia to tokens, see the [module](self) documentation
+pub struct TriviaTokenizer<'src> {
+    src: &'src str,
+    pieces: Peekable<Pieces<'src>>,
+    finished: bool,
+}
+
+impl<'src> TriviaTokenizer<'src> {
+    pub fn new<S: AsRef<str> + ?Sized>(source: &'src S) -> Self {
+        let src = source.as_ref();
+        TriviaTokenizer {
+            src,
+            pieces: Pieces {
+                src,
+                tokenizer: Tokenizer::new(src),
+                pos: 0,
+                queue: VecDeque::new(),
+            }
+            .peekable(),
+            finished: false,
+        }
+    }
+
+    fn trailing(&mut self) -> Vec<Trivia> {
+        let mut trailing = Vec::new();
+        while let Some(Piece::Trivia(trivia)) = self.pieces.peek() {
+            let text = &self.src[trivia.span.start..trivia.span.end];
+            if text.starts_with('\n') {
+                break;
+            }
+            trailing.push(*trivia);
+            self.pieces.next();
+            // a comment spanning lines ends the line of the token
+            if text.contains('\n') {
+                break;
+            }
+        }
+        trailing
+    }
+}
+
+impl Iterator for TriviaTokenizer<'_> {
+    type Item = TriviaToken;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut leading = Vec::new();
+        loop {
+            match self.pieces.next() {
+                Some(Piece::Trivia(trivia)) => leading.push(trivia),
+                Some(Piece::Token(token)) => {
+                    return Some(TriviaToken {
+                        leading,
+                        token,
+                        trailing: self.trailing(),
+                    })
+                }
+                None => {
+                    self.finished = true;
+                    let end = self.src.len();
+                    return Some(TriviaToken {
+                        leading,
+                        token: Ok(Token::new(TokenTy::EOF, Span { start: end, end })),
+                        trailing: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trivia::{Trivia, TriviaTokenizer};
+    use testsuite::unittest;
+
+    unittest!(round_trip, |_, src| {
+        let tokens = TriviaTokenizer::new(src).collect::<Vec<_>>();
+        let text = tokens
+            .iter()
+            .map(|token| token.text(src))
+            .collect::<String>();
+        assert_eq!(text, src);
+
+        let trivia = |trivia: &[Trivia]| {
+            trivia
+                .iter()
+                .map(|trivia| {
+                    format!(
+                        "{:?}({:?})",
+                        trivia.kind,
+                        &src[trivia.span.start..trivia.span.end]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let lines = tokens
+            .iter()
+            .map(|token| {
+                let span = token.token_span();
+                format!(
+                    "[{}] {:?} [{}]",
+                    trivia(&token.leading),
+                    &src[span.start..span.end],
+                    trivia(&token.trailing)
+                )
+            })
+            .collect::<Vec<_>>();
+        insta::assert_snapshot!(lines.join("\n"));
+    });
+}
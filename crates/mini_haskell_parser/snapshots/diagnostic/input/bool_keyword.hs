@@ -0,0 +1,2 @@
+bool b = true;
+bool c = b & F;
@@ -0,0 +1,2 @@
+nat x = 00;
+nat y = x + 1;
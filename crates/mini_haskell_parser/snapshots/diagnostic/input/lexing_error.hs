@@ -0,0 +1,2 @@
+nat x = 1 - 2;
+bool y = T ? $ : F;
\ No newline at end of file
@@ -0,0 +1,7 @@
+nat a = 1 + ;
+bool b = (1 < ) & T;
+func f (x nat) -> nat = x;
+nat c = 1
+nat d = 2 ? 3;
+) 4 ;
+bool e = F;
\ No newline at end of file
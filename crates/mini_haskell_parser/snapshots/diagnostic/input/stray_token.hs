@@ -0,0 +1,2 @@
+nat x = 1 )
+nat y = 2;
@@ -0,0 +1,4 @@
+nat x = 1 );
+nat y = T ? 1 0;
+func f (a nat) -> nat = a;
+nat w = (1 + 2;
\ No newline at end of file
@@ -0,0 +1 @@
+nat x = T ? (1 + 2 : 3;
\ No newline at end of file
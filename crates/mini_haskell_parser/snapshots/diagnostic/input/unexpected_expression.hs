@@ -1 +1 @@
-T ? :
\ No newline at end of file
+bool x = T ? :;
\ No newline at end of file
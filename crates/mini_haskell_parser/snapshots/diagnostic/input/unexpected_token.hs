@@ -1 +1 @@
-T ? 1 ?
\ No newline at end of file
+nat x = T ? 1 ?;
\ No newline at end of file
@@ -0,0 +1,3 @@
+func add (a : nat) (b : nat) -> nat = a + b;
+func twice (f : nat -> nat) (n : nat) -> nat = f n + f n;
+nat four = add 1 3 < 5 ? twice inc 2 : 0;
\ No newline at end of file
@@ -0,0 +1,7 @@
+[ Increment a natural number ]
+[ by one ]
+func inc (n : nat) -> nat = n [the argument] + 1; [ trailing ]
+
+nat x = 1 [note] + 2;
+[ doc of y ] nat y = inc [ call ] x;
+[ dangling ]
\ No newline at end of file
@@ -0,0 +1,3 @@
+bool flag = T;
+func pick (b : bool) (n : nat) -> nat = T ? 1 : 2;
+func apply (f : (nat -> bool) -> nat) (g : nat -> nat -> nat) -> bool = F;
\ No newline at end of file
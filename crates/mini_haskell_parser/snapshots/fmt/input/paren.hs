@@ -0,0 +1,2 @@
+func twice (f : nat -> nat) (n : nat) -> nat = f (f n);
+nat six = (T ? 1 : 2) + (twice (add 1) 2);
\ No newline at end of file
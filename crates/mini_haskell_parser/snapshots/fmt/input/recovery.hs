@@ -0,0 +1,4 @@
+nat a = 1 + ;
+bool b = (1 < ) & T;
+nat c = 1
+nat d = 2;
\ No newline at end of file
@@ -0,0 +1,4 @@
+f
+f x
+f 1 T y
+g x + h y
\ No newline at end of file
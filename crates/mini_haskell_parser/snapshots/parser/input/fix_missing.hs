@@ -0,0 +1,6 @@
+nat y = T ? 1 0;
+func f (a nat) -> nat = a;
+func g (b : nat) nat = b;
+nat w = (1 + 2;
+nat u = 3
+bool z = y < w
\ No newline at end of file
@@ -0,0 +1,3 @@
+nat x = 1 );
+nat v = 1 [keep me] ) ;
+func f (a : nat) -> nat = (a + 1));
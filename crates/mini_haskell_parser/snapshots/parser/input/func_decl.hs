@@ -0,0 +1,4 @@
+func one -> nat = 1;
+func pick (b : bool) (n : nat) -> nat = T ? 1 : 2;
+func twice (f : nat -> nat) -> (nat -> nat) = 0;
+func broken (n nat) -> nat = 1;
\ No newline at end of file
@@ -0,0 +1,4 @@
+(1)
+(1 + 2) < 4
+f (g x) (1 + 1)
+(T ? 1 : 2) + 3
\ No newline at end of file
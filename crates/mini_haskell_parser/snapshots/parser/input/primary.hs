@@ -1,3 +1,4 @@
 T
 F
-123
\ No newline at end of file
+123
+foo
\ No newline at end of file
@@ -0,0 +1,3 @@
+bool flag = T;
+nat answer = 40 + 2;
+bool check = 1 < 2 ? T : F;
\ No newline at end of file
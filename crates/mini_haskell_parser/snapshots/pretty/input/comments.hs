@@ -0,0 +1,8 @@
+[ Increment a natural number ]
+[ by one ]
+func inc (n : nat) -> nat = n [the argument] + 1; [ trailing ]
+
+nat x = 1 [note] + 2;
+[ doc of y ] nat y = inc [ call ] x;
+nat z = [ lhs ] (1 [ inner ]) + [ rhs ] 2 [ last ];
+[ dangling ]
\ No newline at end of file
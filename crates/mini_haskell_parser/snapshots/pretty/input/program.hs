@@ -0,0 +1,5 @@
+bool   flag=T;
+func twice(f:nat->nat)(n:nat)->nat=f(f n);
+func pick (g : (nat -> nat) -> nat) -> bool =
+  g (twice (add 1)) < 3 ? T&F : (1=2);
+nat six=(T?1:2)+twice  inc 2;
\ No newline at end of file
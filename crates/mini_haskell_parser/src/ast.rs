@@ -1,21 +1,102 @@
 use mini_haskell_diagnostic::span::Span;
+pub use mini_haskell_lexer::symbol::Symbol;
+use num_bigint::BigUint;
+
+#[derive(Debug)]
+pub struct Program {
+    pub decls: Vec<Decl>,
+    /// Comments after the last declaration
+    pub comments: Vec<Comment>,
+}
+
+#[derive(Debug)]
+pub struct Decl {
+    pub kind: DeclKind,
+    pub span: Span,
+    /// Comments between the previous declaration and this one
+    pub doc: Vec<Comment>,
+    /// Comments between the tokens of the declaration outside of its
+    /// expressions, or following it on its last line
+    pub comments: Vec<Comment>,
+}
+
+/// A `[ ... ]` comment, `text` includes the brackets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum DeclKind {
+    Var(VarDecl),
+    Func(FuncDecl),
+}
+
+#[derive(Debug)]
+pub struct VarDecl {
+    pub ty: Type,
+    pub name: Ident,
+    pub init: Expr,
+}
+
+#[derive(Debug)]
+pub struct FuncDecl {
+    pub name: Ident,
+    pub params: Vec<Param>,
+    pub ret: Type,
+    pub body: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: Ident,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub name: Symbol,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Type {
+    pub kind: TypeKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeKind {
+    Bool,
+    Nat,
+    Func(Box<Type>, Box<Type>),
+}
 
 #[derive(Debug)]
 pub struct Expr {
     pub kind: ExprKind,
     pub span: Span,
+    /// Comments right before or after the expression, outside of its
+    /// sub-expressions
+    pub comments: Vec<Comment>,
 }
 
 #[derive(Debug)]
 pub enum ExprKind {
     Literal(Literal),
+    Var(Ident),
+    Call(CallExpr),
+    Paren(Box<Expr>),
     BinaryExpr(BinaryExpr),
     CondExpr(CondExpr),
+    /// Placeholder for an expression that failed to parse
+    Error,
 }
 
 #[derive(Debug)]
 pub enum Literal {
-    NatureNum(usize),
+    NatureNum(BigUint),
     Bool(bool),
 }
 
@@ -34,6 +115,12 @@ pub struct BinaryExpr {
     pub rhs: Box<Expr>,
 }
 
+#[derive(Debug)]
+pub struct CallExpr {
+    pub callee: Box<Expr>,
+    pub args: Vec<Expr>,
+}
+
 #[derive(Debug)]
 pub struct CondExpr {
     pub condition: Box<Expr>,
@@ -43,6 +130,21 @@ pub struct CondExpr {
 
 impl Expr {
     pub fn new(kind: ExprKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            comments: Vec::new(),
+        }
+    }
+}
+
+impl Decl {
+    pub fn new(kind: DeclKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            doc: Vec::new(),
+            comments: Vec::new(),
+        }
     }
 }
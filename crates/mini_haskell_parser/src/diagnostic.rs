@@ -4,8 +4,8 @@ mod test {
     use mini_haskell_diagnostic::diagnostic::diagnostic;
     use testsuite::unittest;
 
-    unittest!(unexpected_token, |path, _| {
-        let result = diagnostic(path, |src| {
+    unittest!(unexpected_token, |path, src| {
+        let result = diagnostic(path, src, |src| {
             let (_, errors) = Parser::parse(src);
             errors
         });
@@ -13,8 +13,80 @@ mod test {
         insta::assert_snapshot!(result);
     });
 
-    unittest!(unexpected_expression, |path, _| {
-        let result = diagnostic(path, |src| {
+    unittest!(unexpected_expression, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(unclosed_paren, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(multiple_errors, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(lexing_error, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(leading_zeros, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(bool_keyword, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(suggestions, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(stray_token, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (_, errors) = Parser::parse(src);
+            errors
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(unexpected_eof, |path, src| {
+        let result = diagnostic(path, src, |src| {
             let (_, errors) = Parser::parse(src);
             errors
         });
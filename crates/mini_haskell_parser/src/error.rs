@@ -1,22 +1,71 @@
 use miette::Diagnostic;
+use mini_haskell_diagnostic::fix::Suggestion;
 use mini_haskell_diagnostic::span::Span;
+use mini_haskell_lexer::error::LexingError;
 use thiserror::Error;
 
 #[derive(Diagnostic, Error, Debug, Eq, PartialEq, Copy, Clone)]
 pub enum SyntaxError {
     #[error("Unexpected EOF")]
-    UnexpectedEOF,
+    #[diagnostic(code(MH0002))]
+    UnexpectedEOF {
+        #[label = "The program ends here"]
+        span: Span,
+    },
     #[error("SyntaxError: Unexpected token")]
+    #[diagnostic(code(MH0003))]
     UnexpectedToken {
         #[label = "Expected {expected}, found {found}"]
         span: Span,
         expected: &'static str,
         found: &'static str,
+        #[help]
+        suggestion: Option<Suggestion>,
+    },
+    #[error("SyntaxError: Unclosed parenthesis")]
+    #[diagnostic(code(MH0004))]
+    UnclosedParen {
+        #[label = "This parenthesis is never closed"]
+        span: Span,
+        #[help]
+        suggestion: Option<Suggestion>,
     },
     #[error("SyntaxError: Unexpected AST")]
+    #[diagnostic(code(MH0005))]
     Expected {
         #[label = "Expected {expected}"]
         span: Span,
         expected: &'static str,
     },
 }
+
+impl SyntaxError {
+    /// The machine-applicable fix of this error, if any
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self {
+            SyntaxError::UnexpectedToken { suggestion, .. }
+            | SyntaxError::UnclosedParen { suggestion, .. } => *suggestion,
+            _ => None,
+        }
+    }
+}
+
+/// Every error the parser can report, lexing errors included
+#[derive(Diagnostic, Error, Debug, PartialEq, Clone)]
+pub enum ParseError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Lexing(#[from] LexingError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Syntax(#[from] SyntaxError),
+}
+
+impl ParseError {
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self {
+            ParseError::Lexing(err) => err.suggestion(),
+            ParseError::Syntax(err) => err.suggestion(),
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use crate::ast::{BinaryExpr, BinaryOp, CondExpr, Expr, ExprKind, Literal};
+use crate::ast::{
+    BinaryExpr, BinaryOp, CallExpr, CondExpr, Decl, DeclKind, Expr, ExprKind, FuncDecl, Literal,
+    Param, Program, TypeKind, VarDecl,
+};
 use debug_tree::scoped_branch::ScopedBranch;
 use debug_tree::TreeBuilder;
+use std::fmt::{Display, Formatter};
 
 pub struct AstViewBuilder {
     builder: TreeBuilder,
@@ -32,6 +36,12 @@ impl AstViewBuilder {
     }
 }
 
+impl Default for AstViewBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub trait AstView {
     fn view(&self, f: &mut AstViewBuilder);
 
@@ -42,12 +52,76 @@ pub trait AstView {
     }
 }
 
+impl AstView for Program {
+    fn view(&self, f: &mut AstViewBuilder) {
+        for decl in &self.decls {
+            decl.view(f);
+        }
+        for comment in &self.comments {
+            f.leaf(&format!("comment: {}", comment.text));
+        }
+    }
+}
+
+impl AstView for Decl {
+    fn view(&self, f: &mut AstViewBuilder) {
+        for comment in &self.doc {
+            f.leaf(&format!("doc: {}", comment.text));
+        }
+        match &self.kind {
+            DeclKind::Var(decl) => f.node("var-decl", decl),
+            DeclKind::Func(decl) => f.node("func-decl", decl),
+        };
+        for comment in &self.comments {
+            f.leaf(&format!("comment: {}", comment.text));
+        }
+    }
+}
+
+impl AstView for VarDecl {
+    fn view(&self, f: &mut AstViewBuilder) {
+        f.leaf(&format!("name: {}", self.name.name))
+            .leaf(&format!("type: {}", self.ty.kind))
+            .node("init", &self.init);
+    }
+}
+
+impl AstView for FuncDecl {
+    fn view(&self, f: &mut AstViewBuilder) {
+        f.leaf(&format!("name: {}", self.name.name))
+            .node("params", &self.params)
+            .leaf(&format!("return: {}", self.ret.kind))
+            .node("body", &self.body);
+    }
+}
+
+impl AstView for Vec<Param> {
+    fn view(&self, f: &mut AstViewBuilder) {
+        for param in self {
+            f.leaf(&format!("{}: {}", param.name.name, param.ty.kind));
+        }
+    }
+}
+
 impl AstView for Expr {
     fn view(&self, f: &mut AstViewBuilder) {
         match &self.kind {
             ExprKind::Literal(lit) => lit.view(f),
+            ExprKind::Var(ident) => {
+                f.leaf(&format!("Var({})", ident.name));
+            }
+            ExprKind::Call(expr) => expr.view(f),
+            ExprKind::Paren(expr) => {
+                f.node("paren", expr);
+            }
             ExprKind::BinaryExpr(expr) => expr.view(f),
             ExprKind::CondExpr(expr) => expr.view(f),
+            ExprKind::Error => {
+                f.leaf("Error");
+            }
+        }
+        for comment in &self.comments {
+            f.leaf(&format!("comment: {}", comment.text));
         }
     }
 }
@@ -73,6 +147,20 @@ impl AstView for BinaryExpr {
     }
 }
 
+impl AstView for CallExpr {
+    fn view(&self, f: &mut AstViewBuilder) {
+        f.node("callee", &self.callee).node("args", &self.args);
+    }
+}
+
+impl AstView for Vec<Expr> {
+    fn view(&self, f: &mut AstViewBuilder) {
+        for expr in self {
+            expr.view(f);
+        }
+    }
+}
+
 impl AstView for CondExpr {
     fn view(&self, f: &mut AstViewBuilder) {
         f.node("condition", &self.condition)
@@ -88,7 +176,7 @@ impl AstView for Box<Expr> {
 }
 
 impl BinaryOp {
-    fn raw(&self) -> &'static str {
+    pub(crate) fn raw(&self) -> &'static str {
         match self {
             BinaryOp::Plus => "+",
             BinaryOp::Ampersand => "&",
@@ -98,6 +186,19 @@ impl BinaryOp {
     }
 }
 
+impl Display for TypeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeKind::Bool => f.write_str("bool"),
+            TypeKind::Nat => f.write_str("nat"),
+            TypeKind::Func(param, ret) => match param.kind {
+                TypeKind::Func(..) => write!(f, "({}) -> {}", param.kind, ret.kind),
+                _ => write!(f, "{} -> {}", param.kind, ret.kind),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::fmt::AstView;
@@ -108,4 +209,34 @@ mod tests {
         let ast = Parser::new(&src).conditional().unwrap().ast();
         insta::assert_snapshot!(ast);
     });
+
+    unittest!(program, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
+
+    unittest!(func_decl, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
+
+    unittest!(application, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
+
+    unittest!(paren, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
+
+    unittest!(comments, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
+
+    unittest!(recovery, |_, src| {
+        let ast = Parser::new(&src).program().ast();
+        insta::assert_snapshot!(ast);
+    });
 }
@@ -3,3 +3,4 @@ mod diagnostic;
 pub mod error;
 pub mod fmt;
 pub mod parser;
+pub mod pretty;
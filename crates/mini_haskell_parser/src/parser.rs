@@ -1,40 +1,140 @@
-use crate::ast::{BinaryExpr, BinaryOp, CondExpr, Expr, ExprKind, Literal};
-use crate::error::SyntaxError;
+use crate::ast::{
+    BinaryExpr, BinaryOp, CallExpr, Comment, CondExpr, Decl, DeclKind, Expr, ExprKind, FuncDecl,
+    Ident, Literal, Param, Program, Type, TypeKind, VarDecl,
+};
+use crate::error::{ParseError, SyntaxError};
+use mini_haskell_diagnostic::fix::Suggestion;
 use mini_haskell_diagnostic::span::Span;
 use mini_haskell_lexer::lexer::{Token, TokenTy, Tokenizer};
 use std::iter::Peekable;
 
 pub struct Parser<'src> {
+    src: &'src str,
     tokenizer: Peekable<Tokenizer<'src>>,
+    // span of the most recently consumed token
+    last: Span,
+    errors: Vec<ParseError>,
+    // comments skipped since the last declaration
+    comments: Vec<Comment>,
 }
 
 impl<'src> Parser<'src> {
     pub fn new<S: AsRef<str> + ?Sized>(src: &'src S) -> Self {
         Self {
+            src: src.as_ref(),
             tokenizer: Tokenizer::new(src).peekable(),
+            last: Span { start: 0, end: 0 },
+            errors: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
-    pub fn parse<S: AsRef<str> + ?Sized>(src: &'src S) -> (Option<Expr>, Vec<SyntaxError>) {
+    pub fn parse<S: AsRef<str> + ?Sized>(src: &'src S) -> (Program, Vec<ParseError>) {
         let mut parser = Parser::new(src);
-        let mut errors: Vec<SyntaxError> = Vec::new();
-        let mut expr: Option<Expr> = None;
+        let program = parser.program();
+        (program, parser.errors)
+    }
 
-        match parser.conditional() {
-            Ok(e) => expr = Some(e),
-            Err(err) => errors.push(err),
-        }
-        (expr, errors)
+    /// Errors recovered from so far
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
     }
 
     fn consume(&mut self, ty: TokenTy) -> Result<Token, SyntaxError> {
         match self.peek_type()? {
             found if found == ty => Ok(self.advance()?),
-            found => Err(SyntaxError::UnexpectedToken {
-                span: self.peek_span()?,
-                expected: ty.name(),
-                found: found.name(),
-            }),
+            found => {
+                let span = match found {
+                    TokenTy::EOF => Span {
+                        start: self.last.end,
+                        end: self.last.end,
+                    },
+                    _ => self.peek_span()?,
+                };
+                let suggestion = self.suggest(&ty, &found, span);
+                Err(SyntaxError::UnexpectedToken {
+                    // point where `ty` is missing rather than at what follows
+                    span: match suggestion {
+                        Some(suggestion) if suggestion.span.start == suggestion.span.end => {
+                            suggestion.span
+                        }
+                        _ => span,
+                    },
+                    expected: ty.name(),
+                    found: found.name(),
+                    suggestion,
+                })
+            }
+        }
+    }
+
+    /// Suggest a fix for finding `found` at `span` instead of `expected`:
+    /// remove it if `expected` comes right after it, insert `expected` if it
+    /// is punctuation `found` may follow. Any other token is left alone, the
+    /// fix is not obvious.
+    fn suggest(&mut self, expected: &TokenTy, found: &TokenTy, span: Span) -> Option<Suggestion> {
+        let mut lookahead = Tokenizer::new(&self.src[span.end..])
+            .filter_map(Result::ok)
+            .filter(|token| !matches!(token.ty, TokenTy::Comment));
+        if lookahead.next().is_some_and(|next| &next.ty == expected) {
+            // the spaces next to the stray token go along with it, preferably
+            // those in front so that a following `;` moves up to its place
+            let blank = [' ', '\t'];
+            let before = self.src[..span.start].trim_end_matches(blank).len();
+            let after = span.end + self.src[span.end..].len()
+                - self.src[span.end..].trim_start_matches(blank).len();
+            return Some(Suggestion::remove(match before < span.start {
+                true => Span {
+                    start: before,
+                    end: span.end,
+                },
+                false => Span {
+                    start: span.start,
+                    end: after,
+                },
+            }));
+        }
+        if !Self::may_follow(expected, found) {
+            return None;
+        }
+        let text = match expected {
+            TokenTy::Semicolon => ";",
+            TokenTy::RightParen => ")",
+            TokenTy::Colon => " :",
+            TokenTy::Equal => " =",
+            TokenTy::Arrow => " ->",
+            _ => return None,
+        };
+        Some(Suggestion::insert(self.last.end, text))
+    }
+
+    /// Whether `found` may come right after `expected`, where `expected` is
+    /// missing if `found` does
+    fn may_follow(expected: &TokenTy, found: &TokenTy) -> bool {
+        match expected {
+            TokenTy::Semicolon => matches!(
+                found,
+                TokenTy::BoolDecl | TokenTy::Nat | TokenTy::Func | TokenTy::EOF
+            ),
+            TokenTy::RightParen => !matches!(found, TokenTy::Func | TokenTy::EOF),
+            TokenTy::Colon => {
+                Self::starts_primary(found) || matches!(found, TokenTy::BoolDecl | TokenTy::Nat)
+            }
+            TokenTy::Equal => Self::starts_primary(found),
+            TokenTy::Arrow => {
+                matches!(found, TokenTy::BoolDecl | TokenTy::Nat | TokenTy::LeftParen)
+            }
+            _ => false,
+        }
+    }
+
+    /// Error for running out of tokens, pointing at the end of the source
+    fn eof(&self) -> SyntaxError {
+        SyntaxError::UnexpectedEOF {
+            span: Span {
+                start: self.src.len(),
+                end: self.src.len(),
+            },
         }
     }
 
@@ -50,10 +150,12 @@ impl<'src> Parser<'src> {
         loop {
             match self.tokenizer.peek() {
                 None => return Ok(TokenTy::EOF),
+                Some(Ok(Token {
+                    ty: TokenTy::Comment,
+                    ..
+                })) => self.skip_comment(),
                 Some(Ok(token)) => return Ok(token.ty.clone()),
-                Some(Err(_)) => {
-                    self.tokenizer.next();
-                }
+                Some(Err(_)) => self.skip_invalid(),
             }
         }
     }
@@ -61,11 +163,13 @@ impl<'src> Parser<'src> {
     fn peek_span(&mut self) -> Result<Span, SyntaxError> {
         loop {
             match self.tokenizer.peek() {
-                None => return Err(SyntaxError::UnexpectedEOF),
+                None => return Err(self.eof()),
+                Some(Ok(Token {
+                    ty: TokenTy::Comment,
+                    ..
+                })) => self.skip_comment(),
                 Some(Ok(token)) => return Ok(token.span),
-                Some(Err(_)) => {
-                    self.tokenizer.next();
-                }
+                Some(Err(_)) => self.skip_invalid(),
             }
         }
     }
@@ -73,21 +177,322 @@ impl<'src> Parser<'src> {
     fn advance(&mut self) -> Result<Token, SyntaxError> {
         loop {
             match self.tokenizer.peek() {
-                None => return Err(SyntaxError::UnexpectedEOF),
-                Some(Ok(_)) => return Ok(self.tokenizer.next().unwrap().unwrap()),
-                Some(Err(_)) => {
-                    self.tokenizer.next();
+                None => return Err(self.eof()),
+                Some(Ok(Token {
+                    ty: TokenTy::Comment,
+                    ..
+                })) => self.skip_comment(),
+                Some(Ok(_)) => {
+                    let token = self.tokenizer.next().unwrap().unwrap();
+                    self.last = token.span;
+                    return Ok(token);
+                }
+                Some(Err(_)) => self.skip_invalid(),
+            }
+        }
+    }
+
+    // invalid tokens are reported and then treated as the token they stand
+    // for, or as if they were not there
+    fn skip_invalid(&mut self) {
+        let Some(Err(err)) = self.tokenizer.peek() else {
+            return;
+        };
+        let err = err.clone();
+        match err.recovery() {
+            Some(ty) => *self.tokenizer.peek_mut().unwrap() = Ok(Token::new(ty, err.span())),
+            None => {
+                self.tokenizer.next();
+            }
+        }
+        self.errors.push(err.into());
+    }
+
+    // comments are kept aside to be attached to the expression or declaration
+    // around them
+    fn skip_comment(&mut self) {
+        if let Some(Ok(Token {
+            ty: TokenTy::Comment,
+            span,
+        })) = self.tokenizer.next()
+        {
+            let text = self.src[span.start..span.end].to_owned();
+            self.comments.push(Comment { text, span });
+        }
+    }
+
+    /// Take the comments skipped so far that start before `end`, or on the
+    /// same line as `end`
+    fn take_comments(&mut self, end: usize) -> Vec<Comment> {
+        let src = self.src;
+        let split = self
+            .comments
+            .iter()
+            .position(|comment| {
+                comment.span.start >= end && src[end..comment.span.start].contains('\n')
+            })
+            .unwrap_or(self.comments.len());
+        self.comments.drain(..split).collect()
+    }
+
+    fn identifier(&mut self) -> Result<Ident, SyntaxError> {
+        match self.peek_type()? {
+            TokenTy::Identifier(name) => Ok(Ident {
+                name,
+                span: self.advance()?.span,
+            }),
+            found => Err(SyntaxError::UnexpectedToken {
+                span: self.peek_span()?,
+                expected: "identifier",
+                found: found.name(),
+                suggestion: None,
+            }),
+        }
+    }
+
+    /// Skip tokens until a synchronisation point is reached: a `;`, an unmatched `)`,
+    /// a declaration keyword or EOF. The synchronising token itself is not consumed.
+    fn synchronize(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.peek_type() {
+                Ok(TokenTy::Semicolon | TokenTy::BoolDecl | TokenTy::Nat | TokenTy::Func)
+                | Ok(TokenTy::EOF)
+                | Err(_) => return,
+                Ok(TokenTy::RightParen) if depth == 0 => return,
+                Ok(TokenTy::RightParen) => depth -= 1,
+                Ok(TokenTy::LeftParen) => depth += 1,
+                Ok(_) => {}
+            }
+            let _ = self.advance();
+        }
+    }
+
+    /// Skip the rest of a malformed declaration. `bool` and `nat` also appear as
+    /// types inside a declaration header, so only `;` and `func` are reliable
+    /// places to resume at this level.
+    fn synchronize_declaration(&mut self) {
+        loop {
+            match self.peek_type() {
+                Ok(TokenTy::Func | TokenTy::EOF) | Err(_) => return,
+                Ok(TokenTy::Semicolon) => {
+                    let _ = self.advance();
+                    return;
+                }
+                Ok(_) => {
+                    let _ = self.advance();
                 }
             }
         }
     }
 
+    /// Record `err`, skip to the next synchronisation point and return an
+    /// [`ExprKind::Error`] placeholder covering the skipped tokens.
+    fn recover(&mut self, err: SyntaxError, start: usize) -> Expr {
+        self.errors.push(err.into());
+        self.synchronize();
+        let span = Span {
+            start,
+            end: self.last.end.max(start),
+        };
+        Expr::new(ExprKind::Error, span)
+    }
+
+    fn next_start(&mut self) -> usize {
+        self.peek_span().map_or(self.last.end, |span| span.start)
+    }
+
+    /// parse program according to following rules, recovering from
+    /// malformed declarations:
+    /// ```text
+    /// program  → declaration* EOF ;
+    /// ```
+    pub fn program(&mut self) -> Program {
+        let mut decls = Vec::new();
+        while self.peek_type() != Ok(TokenTy::EOF) {
+            let doc = std::mem::take(&mut self.comments);
+            match self.declaration() {
+                Ok(mut decl) => {
+                    // skip the comments up to the next declaration
+                    let _ = self.peek_type();
+                    decl.doc = doc;
+                    decl.comments = self.take_comments(decl.span.end);
+                    decls.push(decl);
+                }
+                Err(err) => {
+                    self.errors.push(err.into());
+                    self.synchronize_declaration();
+                }
+            }
+        }
+        Program {
+            decls,
+            comments: std::mem::take(&mut self.comments),
+        }
+    }
+
+    /// parse declaration according to following rules:
+    /// ```text
+    /// declaration  → var_decl | func_decl ;
+    /// ```
+    pub fn declaration(&mut self) -> Result<Decl, SyntaxError> {
+        match self.peek_type()? {
+            TokenTy::BoolDecl | TokenTy::Nat => self.var_decl(),
+            TokenTy::Func => self.func_decl(),
+            _ => Err(SyntaxError::Expected {
+                span: self.peek_span()?,
+                expected: "declaration",
+            }),
+        }
+    }
+
+    /// parse variable declaration according to following rules:
+    /// ```text
+    /// var_decl  → ( "bool" | "nat" ) IDENTIFIER "=" expression ";" ;
+    /// ```
+    pub fn var_decl(&mut self) -> Result<Decl, SyntaxError> {
+        let ty = self.var_type()?;
+        let name = self.identifier()?;
+        self.consume(TokenTy::Equal)?;
+        let (init, end) = self.decl_body();
+        let span = Span {
+            start: ty.span.start,
+            end,
+        };
+        Ok(Decl::new(DeclKind::Var(VarDecl { ty, name, init }), span))
+    }
+
+    /// parse function declaration according to following rules:
+    /// ```text
+    /// func_decl  → "func" IDENTIFIER param* "->" type "=" expression ";" ;
+    /// param      → "(" IDENTIFIER ":" type ")" ;
+    /// ```
+    pub fn func_decl(&mut self) -> Result<Decl, SyntaxError> {
+        let func = self.consume(TokenTy::Func)?;
+        let name = self.identifier()?;
+        let mut params = Vec::new();
+        while self.consume_if(TokenTy::LeftParen) {
+            let name = self.identifier()?;
+            self.consume(TokenTy::Colon)?;
+            let ty = self.ty()?;
+            self.consume(TokenTy::RightParen)?;
+            params.push(Param { name, ty });
+        }
+        self.consume(TokenTy::Arrow)?;
+        let ret = self.ty()?;
+        self.consume(TokenTy::Equal)?;
+        let (body, end) = self.decl_body();
+        let span = Span {
+            start: func.span.start,
+            end,
+        };
+        Ok(Decl::new(
+            DeclKind::Func(FuncDecl {
+                name,
+                params,
+                ret,
+                body,
+            }),
+            span,
+        ))
+    }
+
+    /// Parse the `expression ";"` tail of a declaration, returning the expression
+    /// and the end offset of the declaration. A malformed expression is replaced
+    /// by an [`ExprKind::Error`] placeholder and a missing `;` is only reported.
+    fn decl_body(&mut self) -> (Expr, usize) {
+        let start = self.next_start();
+        let expr = match self.expression() {
+            Ok(expr) => expr,
+            Err(err) => {
+                let expr = self.recover(err, start);
+                // the error has been reported, a missing `;` is not worth another one
+                self.consume_if(TokenTy::Semicolon);
+                return (expr, self.last.end.max(start));
+            }
+        };
+        match self.consume(TokenTy::Semicolon) {
+            Ok(semicolon) => (expr, semicolon.span.end),
+            Err(err) => {
+                self.errors.push(err.into());
+                // keep the declaration, the next one most likely starts right
+                // here unless stray tokens precede the `;`
+                loop {
+                    match self.peek_type() {
+                        Ok(TokenTy::BoolDecl | TokenTy::Nat | TokenTy::Func)
+                        | Ok(TokenTy::EOF)
+                        | Err(_) => break,
+                        Ok(TokenTy::Semicolon) => {
+                            let _ = self.advance();
+                            break;
+                        }
+                        Ok(_) => {
+                            let _ = self.advance();
+                        }
+                    }
+                }
+                (expr, self.last.end)
+            }
+        }
+    }
+
+    /// parse type according to following rules:
+    /// ```text
+    /// type  → ( "bool" | "nat" | "(" type ")" ) ( "->" type )? ;
+    /// ```
+    fn ty(&mut self) -> Result<Type, SyntaxError> {
+        let param = match self.peek_type()? {
+            TokenTy::LeftParen => {
+                let open = self.advance()?;
+                let inner = self.ty()?;
+                let close = self.consume(TokenTy::RightParen)?;
+                Type {
+                    kind: inner.kind,
+                    span: Span {
+                        start: open.span.start,
+                        end: close.span.end,
+                    },
+                }
+            }
+            _ => self.var_type()?,
+        };
+        if !self.consume_if(TokenTy::Arrow) {
+            return Ok(param);
+        }
+        let ret = self.ty()?;
+        let span = Span {
+            start: param.span.start,
+            end: ret.span.end,
+        };
+        Ok(Type {
+            kind: TypeKind::Func(Box::new(param), Box::new(ret)),
+            span,
+        })
+    }
+
+    fn var_type(&mut self) -> Result<Type, SyntaxError> {
+        let kind = match self.peek_type()? {
+            TokenTy::BoolDecl => TypeKind::Bool,
+            TokenTy::Nat => TypeKind::Nat,
+            _ => {
+                return Err(SyntaxError::Expected {
+                    span: self.peek_span()?,
+                    expected: "type",
+                })
+            }
+        };
+        Ok(Type {
+            kind,
+            span: self.advance()?.span,
+        })
+    }
+
     /// parse expression according to following rules:
     /// ```text
     /// expression  → conditional ;
     /// ```
     pub fn expression(&mut self) -> Result<Expr, SyntaxError> {
-        todo!()
+        self.conditional()
     }
 
     /// parse conditional expression according to following rules:
@@ -100,7 +505,31 @@ impl<'src> Parser<'src> {
             TokenTy::QuestionMark => {
                 self.consume(TokenTy::QuestionMark)?;
                 let then_branch = self.logical()?;
-                self.consume(TokenTy::Colon)?;
+                self.consume(TokenTy::Colon)
+                    .map_err(|err| match (err, &then_branch.kind) {
+                        // `c ? a b` is parsed as an application, `b` is most likely the else branch
+                        (
+                            SyntaxError::UnexpectedToken {
+                                span,
+                                expected,
+                                found,
+                                ..
+                            },
+                            ExprKind::Call(call),
+                        ) => {
+                            let suggestion = call
+                                .args
+                                .last()
+                                .map(|arg| Suggestion::insert(arg.span.start, ": "));
+                            SyntaxError::UnexpectedToken {
+                                span: suggestion.map_or(span, |suggestion| suggestion.span),
+                                expected,
+                                found,
+                                suggestion,
+                            }
+                        }
+                        (err, _) => err,
+                    })?;
                 let else_branch = self.logical()?;
                 let span = Span {
                     start: condition.span.start,
@@ -113,6 +542,7 @@ impl<'src> Parser<'src> {
                         else_branch: Box::new(else_branch),
                     }),
                     span,
+                    comments: Vec::new(),
                 })
             }
             _ => Ok(condition),
@@ -125,20 +555,22 @@ impl<'src> Parser<'src> {
     /// ```
     pub fn logical(&mut self) -> Result<Expr, SyntaxError> {
         let mut expr = self.comparison()?;
-        loop {
-            let token = match self.peek_type()? {
-                TokenTy::Ampersand => self.advance()?,
-                _ => break,
-            };
+        while let TokenTy::Ampersand = self.peek_type()? {
+            self.advance()?;
 
             let rhs = self.comparison()?;
+            let span = Span {
+                start: expr.span.start,
+                end: rhs.span.end,
+            };
             expr = Expr {
                 kind: ExprKind::BinaryExpr(BinaryExpr {
                     lhs: Box::new(expr),
                     op: BinaryOp::Ampersand,
                     rhs: Box::new(rhs),
                 }),
-                span: token.span,
+                span,
+                comments: Vec::new(),
             }
         }
         Ok(expr)
@@ -150,11 +582,8 @@ impl<'src> Parser<'src> {
     /// ```
     pub fn comparison(&mut self) -> Result<Expr, SyntaxError> {
         let mut expr = self.addition()?;
-        loop {
-            let token = match self.peek_type()? {
-                TokenTy::Less | TokenTy::Equal => self.advance()?,
-                _ => break,
-            };
+        while let TokenTy::Less | TokenTy::Equal = self.peek_type()? {
+            let token = self.advance()?;
 
             let op = match token.ty {
                 TokenTy::Less => BinaryOp::Less,
@@ -163,13 +592,18 @@ impl<'src> Parser<'src> {
             };
 
             let rhs = self.addition()?;
+            let span = Span {
+                start: expr.span.start,
+                end: rhs.span.end,
+            };
             expr = Expr {
                 kind: ExprKind::BinaryExpr(BinaryExpr {
                     lhs: Box::new(expr),
                     op,
                     rhs: Box::new(rhs),
                 }),
-                span: token.span,
+                span,
+                comments: Vec::new(),
             }
         }
         Ok(expr)
@@ -177,58 +611,134 @@ impl<'src> Parser<'src> {
 
     /// parse comparison expression according to following rules:
     /// ```text
-    /// addition  → primary ("+" primary)*
+    /// addition  → application ("+" application)*
     /// ```
     pub fn addition(&mut self) -> Result<Expr, SyntaxError> {
-        let mut expr = self.primary()?;
-        loop {
-            let token = match self.peek_type()? {
-                TokenTy::Plus => self.advance()?,
-                _ => break,
-            };
+        let mut expr = self.application()?;
+        while let TokenTy::Plus = self.peek_type()? {
+            self.advance()?;
 
-            let rhs = self.primary()?;
+            let rhs = self.application()?;
+            let span = Span {
+                start: expr.span.start,
+                end: rhs.span.end,
+            };
             expr = Expr {
                 kind: ExprKind::BinaryExpr(BinaryExpr {
                     lhs: Box::new(expr),
                     op: BinaryOp::Plus,
                     rhs: Box::new(rhs),
                 }),
-                span: token.span,
+                span,
+                comments: Vec::new(),
             }
         }
         Ok(expr)
     }
 
+    /// parse function application according to following rules:
+    /// ```text
+    /// application  → primary primary* ;
+    /// ```
+    pub fn application(&mut self) -> Result<Expr, SyntaxError> {
+        let callee = self.primary()?;
+        let mut args = Vec::new();
+        while Self::starts_primary(&self.peek_type()?) {
+            args.push(self.primary()?);
+        }
+        match args.last() {
+            None => Ok(callee),
+            Some(last) => {
+                let span = Span {
+                    start: callee.span.start,
+                    end: last.span.end,
+                };
+                Ok(Expr::new(
+                    ExprKind::Call(CallExpr {
+                        callee: Box::new(callee),
+                        args,
+                    }),
+                    span,
+                ))
+            }
+        }
+    }
+
+    fn starts_primary(ty: &TokenTy) -> bool {
+        matches!(
+            ty,
+            TokenTy::BoolLit(_) | TokenTy::NatLit | TokenTy::Identifier(_) | TokenTy::LeftParen
+        )
+    }
+
     /// parse primary expression according to following rules:
     ///
     /// ```text
-    /// primary  → NAT | "T" | "F" | IDENTIFIER ;
+    /// primary  → NAT | "T" | "F" | IDENTIFIER | "(" expression ")" ;
     /// ```
     fn primary(&mut self) -> Result<Expr, SyntaxError> {
-        match self.peek_type()? {
-            TokenTy::BoolLit(b) => Ok(Expr::new(
-                ExprKind::Literal(Literal::Bool(b)),
-                self.advance()?.span,
-            )),
-            TokenTy::NatLit(n) => Ok(Expr::new(
-                ExprKind::Literal(Literal::NatureNum(n)),
-                self.advance()?.span,
-            )),
-            TokenTy::Identifier(_) => todo!(),
-            _ => Err(SyntaxError::Expected {
-                span: self.peek_span()?,
-                expected: "expression",
-            }),
-        }
+        // the comments skipped since the previous token come before this one
+        let ty = self.peek_type()?;
+        let last = self.last.end;
+        let leading = self
+            .comments
+            .partition_point(|comment| comment.span.start < last);
+        let mut expr = match ty {
+            TokenTy::BoolLit(b) => {
+                Expr::new(ExprKind::Literal(Literal::Bool(b)), self.advance()?.span)
+            }
+            // the lexer only matches digits
+            TokenTy::NatLit => {
+                let span = self.advance()?.span;
+                let n = self.src[span.start..span.end].parse().unwrap();
+                Expr::new(ExprKind::Literal(Literal::NatureNum(n)), span)
+            }
+            TokenTy::Identifier(_) => {
+                let ident = self.identifier()?;
+                let span = ident.span;
+                Expr::new(ExprKind::Var(ident), span)
+            }
+            TokenTy::LeftParen => {
+                let open = self.advance()?;
+                let start = self.next_start();
+                let expr = match self.expression() {
+                    Ok(_) if self.peek_type()? != TokenTy::RightParen => {
+                        return Err(SyntaxError::UnclosedParen {
+                            span: open.span,
+                            suggestion: Some(Suggestion::insert(self.last.end, ")")),
+                        });
+                    }
+                    Ok(expr) => expr,
+                    Err(err) => self.recover(err, start),
+                };
+                self.consume_if(TokenTy::RightParen);
+                let span = Span {
+                    start: open.span.start,
+                    end: self.last.end,
+                };
+                Expr::new(ExprKind::Paren(Box::new(expr)), span)
+            }
+            _ => {
+                return Err(SyntaxError::Expected {
+                    span: self.peek_span()?,
+                    expected: "expression",
+                })
+            }
+        };
+        // skip the comments up to the next token, they come after this one
+        self.peek_type()?;
+        expr.comments = self.comments.split_off(leading);
+        Ok(expr)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::ParseError;
     use crate::parser::Parser;
     use crate::parser::SyntaxError;
     use crate::parser::Token;
+    use mini_haskell_diagnostic::fix;
     use mini_haskell_lexer::lexer::TokenTy::NatLit;
     use testsuite::unittest;
 
@@ -237,7 +747,7 @@ mod tests {
         let mut result = Vec::<Result<Token, SyntaxError>>::new();
         loop {
             match parser.advance() {
-                Err(SyntaxError::UnexpectedEOF) => break,
+                Err(SyntaxError::UnexpectedEOF { .. }) => break,
                 Ok(tok) => result.push(Ok(tok)),
                 Err(err) => result.push(Err(err)),
             }
@@ -247,7 +757,7 @@ mod tests {
 
     unittest!(consume, |_, src| {
         let mut parser = Parser::new(src);
-        let result = vec![parser.consume(NatLit(1)), parser.consume(NatLit(2))];
+        let result = vec![parser.consume(NatLit), parser.consume(NatLit)];
         insta::assert_debug_snapshot!(result);
     });
 
@@ -290,4 +800,52 @@ mod tests {
             .collect::<Vec<_>>();
         insta::assert_debug_snapshot!(asts);
     });
+
+    unittest!(program, |_, src| {
+        let program = Parser::parse(src);
+        insta::assert_debug_snapshot!(program);
+    });
+
+    unittest!(application, |_, src| {
+        let asts = src
+            .split('\n')
+            .map(|line| Parser::new(line).application())
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(asts);
+    });
+
+    unittest!(paren, |_, src| {
+        let asts = src
+            .split('\n')
+            .map(|line| Parser::new(line).expression())
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(asts);
+    });
+
+    unittest!(func_decl, |_, src| {
+        let decls = src
+            .split('\n')
+            .map(|line| Parser::new(line).func_decl())
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(decls);
+    });
+
+    /// Apply the suggestions for the errors of `src`, which must fix all of them
+    fn fixed(src: &str) -> String {
+        let (_, errors) = Parser::parse(src);
+        let suggestions = errors.iter().filter_map(ParseError::suggestion);
+        let (fixed, applied) = fix::apply(src, suggestions);
+        assert_eq!(applied, errors.len());
+        let (_, errors) = Parser::parse(&fixed);
+        assert!(errors.is_empty(), "{fixed}\n{errors:#?}");
+        fixed
+    }
+
+    unittest!(fix_stray, |_, src| {
+        insta::assert_snapshot!(fixed(src));
+    });
+
+    unittest!(fix_missing, |_, src| {
+        insta::assert_snapshot!(fixed(src));
+    });
 }
@@ -0,0 +1,133 @@
+//! Render the AST back into mini-haskell source in a canonical layout: one
+//! declaration per line and single spaces between tokens.
+
+use crate::ast::{
+    BinaryExpr, CallExpr, CondExpr, Decl, DeclKind, Expr, ExprKind, FuncDecl, Literal, Program,
+    VarDecl,
+};
+use std::fmt::{Display, Formatter, Result};
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for decl in &self.decls {
+            writeln!(f, "{decl}")?;
+        }
+        for comment in &self.comments {
+            writeln!(f, "{}", comment.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Decl {
+    /// Doc comments are put on their own lines, the other comments at the end
+    /// of the declaration
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for comment in &self.doc {
+            writeln!(f, "{}", comment.text)?;
+        }
+        match &self.kind {
+            DeclKind::Var(decl) => decl.fmt(f)?,
+            DeclKind::Func(decl) => decl.fmt(f)?,
+        }
+        for comment in &self.comments {
+            write!(f, " {}", comment.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for VarDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {} = {};", self.ty.kind, self.name.name, self.init)
+    }
+}
+
+impl Display for FuncDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "func {}", self.name.name)?;
+        for param in &self.params {
+            write!(f, " ({} : {})", param.name.name, param.ty.kind)?;
+        }
+        write!(f, " -> {} = {};", self.ret.kind, self.body)
+    }
+}
+
+impl Display for Expr {
+    /// Comments stay on the side of the expression they were found on
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (before, after) = self.comments.split_at(
+            self.comments
+                .partition_point(|comment| comment.span.start < self.span.start),
+        );
+        for comment in before {
+            write!(f, "{} ", comment.text)?;
+        }
+        match &self.kind {
+            ExprKind::Literal(lit) => lit.fmt(f),
+            ExprKind::Var(ident) => f.write_str(ident.name.as_str()),
+            ExprKind::Call(expr) => expr.fmt(f),
+            ExprKind::Paren(expr) => write!(f, "({expr})"),
+            ExprKind::BinaryExpr(expr) => expr.fmt(f),
+            ExprKind::CondExpr(expr) => expr.fmt(f),
+            ExprKind::Error => f.write_str("<error>"),
+        }?;
+        for comment in after {
+            write!(f, " {}", comment.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Literal::NatureNum(n) => write!(f, "{n}"),
+            Literal::Bool(true) => f.write_str("T"),
+            Literal::Bool(false) => f.write_str("F"),
+        }
+    }
+}
+
+impl Display for CallExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.callee)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for BinaryExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {} {}", self.lhs, self.op.raw(), self.rhs)
+    }
+}
+
+impl Display for CondExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} ? {} : {}",
+            self.condition, self.then_branch, self.else_branch
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use testsuite::unittest;
+
+    unittest!(program, |_, src| {
+        let (program, _) = Parser::parse(src);
+        insta::assert_snapshot!(program.to_string());
+    });
+
+    unittest!(comments, |_, src| {
+        let (program, errors) = Parser::parse(src);
+        assert!(errors.is_empty());
+        insta::assert_snapshot!(program.to_string());
+    });
+}
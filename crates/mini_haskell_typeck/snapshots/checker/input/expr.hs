@@ -0,0 +1,6 @@
+1 + 2
+1 < 2
+T & F
+1 = 2
+T ? 1 : 2
+(T ? F : T) = F
\ No newline at end of file
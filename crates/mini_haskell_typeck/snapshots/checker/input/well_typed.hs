@@ -0,0 +1,6 @@
+func even (n : nat) -> bool = n = 0 ? T : odd (n + 1);
+func odd (n : nat) -> bool = n = 1 ? T : even (n + 1);
+func twice (f : nat -> nat) (n : nat) -> nat = f (f n);
+func inc (n : nat) -> nat = n + 1;
+nat four = twice inc 2;
+bool check = even four & (four < 5 = T);
\ No newline at end of file
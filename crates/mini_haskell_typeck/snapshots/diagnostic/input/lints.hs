@@ -0,0 +1,3 @@
+func first (a : nat) (b : nat) -> nat = a;
+nat x = (T) ? first 1 2 : 0;
+bool y = x < 1 ? F : T;
\ No newline at end of file
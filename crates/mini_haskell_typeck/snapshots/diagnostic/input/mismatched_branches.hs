@@ -0,0 +1,2 @@
+nat a = T ? 1 : F;
+nat b = 1 ? 2 : 3;
\ No newline at end of file
@@ -0,0 +1,6 @@
+nat a = T + 1;
+bool b = 1 & 2;
+bool c = 1 = F;
+nat d = 1 < 2;
+func id (n : nat) -> nat = n;
+bool e = id = id;
\ No newline at end of file
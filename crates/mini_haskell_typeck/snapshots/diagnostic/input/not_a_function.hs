@@ -0,0 +1,4 @@
+nat a = 1 2;
+func add (x : nat) (y : nat) -> nat = x + y;
+nat b = add 1 2 3;
+nat c = add T 2;
\ No newline at end of file
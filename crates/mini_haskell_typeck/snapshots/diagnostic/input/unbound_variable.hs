@@ -0,0 +1,5 @@
+nat a = b + 1;
+nat b = 2;
+func f (x : nat) -> nat = x + y;
+func g (x : nat) (x : bool) -> bool = x;
+bool b = T;
\ No newline at end of file
@@ -0,0 +1,257 @@
+use crate::error::TypeError;
+use crate::ty::Ty;
+use mini_haskell_diagnostic::span::Span;
+use mini_haskell_parser::ast::{
+    BinaryExpr, BinaryOp, CallExpr, CondExpr, DeclKind, Expr, ExprKind, FuncDecl, Ident, Literal,
+    Program, Symbol,
+};
+use std::collections::HashMap;
+
+struct Binding {
+    ty: Ty,
+    span: Span,
+}
+
+pub struct TypeChecker {
+    globals: HashMap<Symbol, Binding>,
+    // parameters of the function being checked
+    locals: HashMap<Symbol, Binding>,
+    errors: Vec<TypeError>,
+}
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        Self {
+            globals: HashMap::new(),
+            locals: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check(program: &Program) -> Vec<TypeError> {
+        let mut checker = TypeChecker::new();
+        checker.program(program);
+        checker.errors
+    }
+
+    /// Check the declarations of `program` in order. Functions are visible
+    /// everywhere so that they can be (mutually) recursive, while variables are
+    /// only visible after their declaration.
+    pub fn program(&mut self, program: &Program) {
+        for decl in &program.decls {
+            if let DeclKind::Func(func) = &decl.kind {
+                self.define(&func.name, Self::signature(func));
+            }
+        }
+
+        for decl in &program.decls {
+            match &decl.kind {
+                DeclKind::Var(var) => {
+                    let expected = Ty::from(&var.ty);
+                    self.expect(&var.init, &expected);
+                    self.define(&var.name, expected);
+                }
+                DeclKind::Func(func) => self.func(func),
+            }
+        }
+    }
+
+    fn signature(func: &FuncDecl) -> Ty {
+        func.params
+            .iter()
+            .rev()
+            .fold(Ty::from(&func.ret), |ret, param| {
+                Ty::Func(Box::new(Ty::from(&param.ty)), Box::new(ret))
+            })
+    }
+
+    fn func(&mut self, func: &FuncDecl) {
+        self.locals.clear();
+        for param in &func.params {
+            let binding = Binding {
+                ty: Ty::from(&param.ty),
+                span: param.name.span,
+            };
+            if let Some(previous) = self.locals.insert(param.name.name.clone(), binding) {
+                self.errors.push(TypeError::Duplicate {
+                    previous: previous.span,
+                    span: param.name.span,
+                    name: param.name.name.clone(),
+                });
+            }
+        }
+        self.expect(&func.body, &Ty::from(&func.ret));
+        self.locals.clear();
+    }
+
+    fn define(&mut self, name: &Ident, ty: Ty) {
+        let binding = Binding {
+            ty,
+            span: name.span,
+        };
+        match self.globals.get(&name.name) {
+            Some(previous) => self.errors.push(TypeError::Duplicate {
+                previous: previous.span,
+                span: name.span,
+                name: name.name.clone(),
+            }),
+            None => {
+                self.globals.insert(name.name.clone(), binding);
+            }
+        }
+    }
+
+    fn expect(&mut self, expr: &Expr, expected: &Ty) {
+        let found = self.expr(expr);
+        if !found.compatible(expected) {
+            self.errors.push(TypeError::Mismatch {
+                span: expr.span,
+                expected: expected.clone(),
+                found,
+            });
+        }
+    }
+
+    pub fn expr(&mut self, expr: &Expr) -> Ty {
+        match &expr.kind {
+            ExprKind::Literal(Literal::NatureNum(_)) => Ty::Nat,
+            ExprKind::Literal(Literal::Bool(_)) => Ty::Bool,
+            ExprKind::Var(ident) => self.var(ident),
+            ExprKind::Call(call) => self.call(call),
+            ExprKind::Paren(expr) => self.expr(expr),
+            ExprKind::BinaryExpr(binary) => self.binary(binary),
+            ExprKind::CondExpr(cond) => self.cond(cond),
+            // already reported by the parser
+            ExprKind::Error => Ty::Error,
+        }
+    }
+
+    fn var(&mut self, ident: &Ident) -> Ty {
+        match self
+            .locals
+            .get(&ident.name)
+            .or_else(|| self.globals.get(&ident.name))
+        {
+            Some(binding) => binding.ty.clone(),
+            None => {
+                self.errors.push(TypeError::Unbound {
+                    span: ident.span,
+                    name: ident.name.clone(),
+                });
+                Ty::Error
+            }
+        }
+    }
+
+    fn call(&mut self, call: &CallExpr) -> Ty {
+        let mut ty = self.expr(&call.callee);
+        // the part of the application that has been given type `ty`
+        let mut applied = call.callee.span;
+        for arg in &call.args {
+            ty = match ty {
+                Ty::Func(param, ret) => {
+                    self.expect(arg, &param);
+                    applied.end = arg.span.end;
+                    *ret
+                }
+                Ty::Error => {
+                    self.expr(arg);
+                    Ty::Error
+                }
+                ty => {
+                    self.errors
+                        .push(TypeError::NotAFunction { span: applied, ty });
+                    return Ty::Error;
+                }
+            };
+        }
+        ty
+    }
+
+    fn binary(&mut self, binary: &BinaryExpr) -> Ty {
+        match binary.op {
+            BinaryOp::Plus => {
+                self.expect(&binary.lhs, &Ty::Nat);
+                self.expect(&binary.rhs, &Ty::Nat);
+                Ty::Nat
+            }
+            BinaryOp::Less => {
+                self.expect(&binary.lhs, &Ty::Nat);
+                self.expect(&binary.rhs, &Ty::Nat);
+                Ty::Bool
+            }
+            BinaryOp::Ampersand => {
+                self.expect(&binary.lhs, &Ty::Bool);
+                self.expect(&binary.rhs, &Ty::Bool);
+                Ty::Bool
+            }
+            BinaryOp::Equal => {
+                let lhs = self.expr(&binary.lhs);
+                let rhs = self.expr(&binary.rhs);
+                if !lhs.compatible(&rhs) {
+                    self.errors.push(TypeError::OperandMismatch {
+                        lhs_span: binary.lhs.span,
+                        lhs_ty: lhs,
+                        rhs_span: binary.rhs.span,
+                        rhs_ty: rhs,
+                    });
+                } else if let ty @ Ty::Func(..) = lhs {
+                    self.errors.push(TypeError::NotComparable {
+                        span: binary.lhs.span,
+                        ty,
+                    });
+                }
+                Ty::Bool
+            }
+        }
+    }
+
+    fn cond(&mut self, cond: &CondExpr) -> Ty {
+        self.expect(&cond.condition, &Ty::Bool);
+        let then_ty = self.expr(&cond.then_branch);
+        let else_ty = self.expr(&cond.else_branch);
+        if !then_ty.compatible(&else_ty) {
+            self.errors.push(TypeError::BranchMismatch {
+                then_span: cond.then_branch.span,
+                then_ty,
+                else_span: cond.else_branch.span,
+                else_ty,
+            });
+            return Ty::Error;
+        }
+        match then_ty {
+            Ty::Error => else_ty,
+            ty => ty,
+        }
+    }
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checker::TypeChecker;
+    use mini_haskell_parser::parser::Parser;
+    use testsuite::unittest;
+
+    unittest!(well_typed, |_, src| {
+        let (program, _) = Parser::parse(src);
+        let errors = TypeChecker::check(&program);
+        insta::assert_debug_snapshot!(errors);
+    });
+
+    unittest!(expr, |_, src| {
+        let types = src
+            .split('\n')
+            .map(|line| {
+                let expr = Parser::new(line).expression().unwrap();
+                TypeChecker::new().expr(&expr).to_string()
+            })
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(types);
+    });
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod test {
+    use crate::checker::TypeChecker;
+    use crate::lint::Linter;
+    use mini_haskell_diagnostic::diagnostic::diagnostic;
+    use mini_haskell_parser::parser::Parser;
+    use testsuite::unittest;
+
+    unittest!(mismatched_types, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            TypeChecker::check(&program)
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(mismatched_branches, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            TypeChecker::check(&program)
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(unbound_variable, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            TypeChecker::check(&program)
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(not_a_function, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            TypeChecker::check(&program)
+        });
+
+        insta::assert_snapshot!(result);
+    });
+
+    unittest!(lints, |path, src| {
+        let result = diagnostic(path, src, |src| {
+            let (program, _) = Parser::parse(src);
+            Linter::lint(&program)
+        });
+
+        insta::assert_snapshot!(result);
+    });
+}
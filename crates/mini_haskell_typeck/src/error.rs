@@ -0,0 +1,67 @@
+use crate::ty::Ty;
+use miette::Diagnostic;
+use mini_haskell_diagnostic::span::Span;
+use mini_haskell_parser::ast::Symbol;
+use thiserror::Error;
+
+#[derive(Diagnostic, Error, Debug, PartialEq, Clone)]
+pub enum TypeError {
+    #[error("TypeError: Mismatched types")]
+    #[diagnostic(code(MH0006))]
+    Mismatch {
+        #[label = "Expected {expected}, found {found}"]
+        span: Span,
+        expected: Ty,
+        found: Ty,
+    },
+    #[error("TypeError: Mismatched branches")]
+    #[diagnostic(code(MH0007))]
+    BranchMismatch {
+        #[label = "This is of type {then_ty}"]
+        then_span: Span,
+        then_ty: Ty,
+        #[label = "But this is of type {else_ty}"]
+        else_span: Span,
+        else_ty: Ty,
+    },
+    #[error("TypeError: Mismatched operands")]
+    #[diagnostic(code(MH0008))]
+    OperandMismatch {
+        #[label = "This is of type {lhs_ty}"]
+        lhs_span: Span,
+        lhs_ty: Ty,
+        #[label = "But this is of type {rhs_ty}"]
+        rhs_span: Span,
+        rhs_ty: Ty,
+    },
+    #[error("TypeError: Functions can not be compared")]
+    #[diagnostic(code(MH0009))]
+    NotComparable {
+        #[label = "This is of type {ty}"]
+        span: Span,
+        ty: Ty,
+    },
+    #[error("TypeError: Not a function")]
+    #[diagnostic(code(MH0010))]
+    NotAFunction {
+        #[label = "This is of type {ty}, it can not be applied"]
+        span: Span,
+        ty: Ty,
+    },
+    #[error("TypeError: Unbound variable")]
+    #[diagnostic(code(MH0011))]
+    Unbound {
+        #[label = "Can not find `{name}` in this scope"]
+        span: Span,
+        name: Symbol,
+    },
+    #[error("TypeError: Duplicate definition")]
+    #[diagnostic(code(MH0012))]
+    Duplicate {
+        #[label = "`{name}` is defined here"]
+        previous: Span,
+        #[label = "And redefined here"]
+        span: Span,
+        name: Symbol,
+    },
+}
@@ -0,0 +1,5 @@
+pub mod checker;
+mod diagnostic;
+pub mod error;
+pub mod lint;
+pub mod ty;
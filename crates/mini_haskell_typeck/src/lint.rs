@@ -0,0 +1,113 @@
+use miette::Diagnostic;
+use mini_haskell_diagnostic::span::Span;
+use mini_haskell_parser::ast::{DeclKind, Expr, ExprKind, FuncDecl, Literal, Program, Symbol};
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Warnings about well typed but suspicious programs, each of them belongs to
+/// a lint of [`mini_haskell_diagnostic::lint::LINTS`]
+#[derive(Diagnostic, Error, Debug, PartialEq, Clone)]
+pub enum Warning {
+    #[error("Unused parameter")]
+    #[diagnostic(
+        code(MH0017),
+        severity(Warning),
+        help("Remove the parameter or use it in the body")
+    )]
+    UnusedParameter {
+        #[label = "`{name}` is never used"]
+        span: Span,
+        name: Symbol,
+    },
+    #[error("Constant condition")]
+    #[diagnostic(code(MH0018), severity(Warning))]
+    ConstantCondition {
+        #[label = "This condition is always {value}"]
+        span: Span,
+        value: &'static str,
+    },
+}
+
+pub struct Linter {
+    warnings: Vec<Warning>,
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self {
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn lint(program: &Program) -> Vec<Warning> {
+        let mut linter = Linter::new();
+        linter.program(program);
+        linter.warnings
+    }
+
+    pub fn program(&mut self, program: &Program) {
+        for decl in &program.decls {
+            match &decl.kind {
+                DeclKind::Var(var) => self.expr(&var.init, &mut HashSet::new()),
+                DeclKind::Func(func) => self.func(func),
+            }
+        }
+    }
+
+    fn func(&mut self, func: &FuncDecl) {
+        let mut used = HashSet::new();
+        self.expr(&func.body, &mut used);
+        for param in &func.params {
+            if !used.contains(&param.name.name) {
+                self.warnings.push(Warning::UnusedParameter {
+                    span: param.name.span,
+                    name: param.name.name.clone(),
+                });
+            }
+        }
+    }
+
+    /// Lint `expr`, collecting the variables it refers to in `used`
+    fn expr(&mut self, expr: &Expr, used: &mut HashSet<Symbol>) {
+        match &expr.kind {
+            ExprKind::Literal(_) | ExprKind::Error => {}
+            ExprKind::Var(ident) => {
+                used.insert(ident.name.clone());
+            }
+            ExprKind::Call(call) => {
+                self.expr(&call.callee, used);
+                call.args.iter().for_each(|arg| self.expr(arg, used));
+            }
+            ExprKind::Paren(expr) => self.expr(expr, used),
+            ExprKind::BinaryExpr(binary) => {
+                self.expr(&binary.lhs, used);
+                self.expr(&binary.rhs, used);
+            }
+            ExprKind::CondExpr(cond) => {
+                if let Some(value) = constant(&cond.condition) {
+                    self.warnings.push(Warning::ConstantCondition {
+                        span: cond.condition.span,
+                        value: if value { "T" } else { "F" },
+                    });
+                }
+                self.expr(&cond.condition, used);
+                self.expr(&cond.then_branch, used);
+                self.expr(&cond.else_branch, used);
+            }
+        }
+    }
+}
+
+fn constant(expr: &Expr) -> Option<bool> {
+    match &expr.kind {
+        ExprKind::Literal(Literal::Bool(value)) => Some(*value),
+        ExprKind::Paren(expr) => constant(expr),
+        _ => None,
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
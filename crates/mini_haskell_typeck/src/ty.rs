@@ -0,0 +1,50 @@
+use mini_haskell_parser::ast::{Type, TypeKind};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ty {
+    Bool,
+    Nat,
+    Func(Box<Ty>, Box<Ty>),
+    /// The type of an expression that has already been reported as ill-typed,
+    /// compatible with every other type to avoid cascading errors
+    Error,
+}
+
+impl Ty {
+    /// Whether a value of type `self` can be used where `other` is expected
+    pub fn compatible(&self, other: &Ty) -> bool {
+        match (self, other) {
+            (Ty::Error, _) | (_, Ty::Error) => true,
+            (Ty::Func(p1, r1), Ty::Func(p2, r2)) => p1.compatible(p2) && r1.compatible(r2),
+            (lhs, rhs) => lhs == rhs,
+        }
+    }
+}
+
+impl From<&Type> for Ty {
+    fn from(ty: &Type) -> Self {
+        match &ty.kind {
+            TypeKind::Bool => Ty::Bool,
+            TypeKind::Nat => Ty::Nat,
+            TypeKind::Func(param, ret) => Ty::Func(
+                Box::new(param.as_ref().into()),
+                Box::new(ret.as_ref().into()),
+            ),
+        }
+    }
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ty::Bool => f.write_str("bool"),
+            Ty::Nat => f.write_str("nat"),
+            Ty::Func(param, ret) => match param.as_ref() {
+                Ty::Func(..) => write!(f, "({param}) -> {ret}"),
+                _ => write!(f, "{param} -> {ret}"),
+            },
+            Ty::Error => f.write_str("{error}"),
+        }
+    }
+}